@@ -0,0 +1,147 @@
+use ndarray::Array2;
+#[cfg(feature = "python")]
+use numpy::{Element, IntoPyArray, PyReadonlyArray2};
+#[cfg(feature = "python")]
+use pyo3::exceptions::PyTypeError;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+use rayon::prelude::*;
+
+use crate::{
+    advance_or_missing, check_len, FilterFloat, KalmanResult, MissingPolicy, ScalarFilter,
+};
+#[cfg(feature = "python")]
+use crate::{PyFilter, PyScalarKalman, PyScalarKalman32, SqrtKalman};
+
+/// Filter every row of `zs` with its own filter, in parallel.
+///
+/// Each filter is left holding the final state of its row. Errors report the failing
+/// row.
+pub fn filter_rows<T: FilterFloat, F: ScalarFilter<T> + Send>(
+    filters: &mut [F],
+    zs: &Array2<T>,
+    policy: MissingPolicy,
+) -> KalmanResult<Array2<T>> {
+    check_len("filters", filters.len(), zs.nrows())?;
+    let views: Vec<_> = zs.outer_iter().collect();
+    let rows = filters
+        .par_iter_mut()
+        .zip(views.par_iter())
+        .enumerate()
+        .map(|(r, (filter, row))| {
+            row.iter()
+                .enumerate()
+                .map(|(i, &z)| advance_or_missing(filter, z, false, policy, i))
+                .collect::<KalmanResult<Vec<T>>>()
+                .map_err(|e| e.in_row(r))
+        })
+        .collect::<KalmanResult<Vec<Vec<T>>>>()?;
+    let flat = rows.into_iter().flatten().collect();
+    Ok(Array2::from_shape_vec(zs.dim(), flat).expect("rows have the input's length"))
+}
+
+/// Filter many independent series, one per row of `v`, on a thread pool with the GIL
+/// released.
+///
+/// `filters` is either a single `ScalarKalman`, `ScalarKalman32` or `SqrtKalman` whose
+/// parameters and initial state are shared by every row (it is not modified), or a
+/// sequence with one filter per row, each of which is advanced like `kfilter`. Missing
+/// samples are handled as in `kfilter`.
+#[cfg(feature = "python")]
+#[pyfunction]
+pub(crate) fn kfilter_batch<'py>(
+    py: Python<'py>,
+    filters: &PyAny,
+    v: &PyAny,
+    missing: Option<&str>,
+) -> PyResult<&'py PyAny> {
+    let policy: MissingPolicy = missing.unwrap_or("predict").parse()?;
+    if let Some(out) = batch_of::<PyScalarKalman, _>(py, filters, v, policy)? {
+        Ok(out)
+    } else if let Some(out) = batch_of::<PyScalarKalman32, _>(py, filters, v, policy)? {
+        Ok(out)
+    } else if let Some(out) = batch_of::<SqrtKalman, _>(py, filters, v, policy)? {
+        Ok(out)
+    } else {
+        Err(PyTypeError::new_err(
+            "filters must be a ScalarKalman, ScalarKalman32 or SqrtKalman, or a sequence of one of them",
+        ))
+    }
+}
+
+/// Run `kfilter_batch` if `filters` holds filters of class `W`, or return `None`.
+#[cfg(feature = "python")]
+fn batch_of<'py, W, T>(
+    py: Python<'py>,
+    filters: &PyAny,
+    v: &PyAny,
+    policy: MissingPolicy,
+) -> PyResult<Option<&'py PyAny>>
+where
+    W: PyFilter,
+    W::Inner: ScalarFilter<T> + Send,
+    T: FilterFloat + Element,
+{
+    let out = if let Ok(shared) = filters.extract::<W>() {
+        let zs = v.extract::<PyReadonlyArray2<T>>()?.as_array().to_owned();
+        let mut rows = vec![shared.inner().clone(); zs.nrows()];
+        py.allow_threads(|| filter_rows(&mut rows, &zs, policy))?
+    } else if let Ok(mut refs) = filters.extract::<Vec<PyRefMut<W>>>() {
+        let zs = v.extract::<PyReadonlyArray2<T>>()?.as_array().to_owned();
+        let mut rows: Vec<W::Inner> = refs.iter().map(|f| f.inner().clone()).collect();
+        let out = py.allow_threads(|| filter_rows(&mut rows, &zs, policy))?;
+        for (r, row) in refs.iter_mut().zip(rows) {
+            *r.inner_mut() = row;
+        }
+        out
+    } else {
+        return Ok(None);
+    };
+    Ok(Some(out.into_pyarray(py).as_ref()))
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::array;
+
+    use super::*;
+    use crate::{Float, KalmanError, ScalarKalman};
+
+    fn filters() -> Vec<ScalarKalman> {
+        (0..3)
+            .map(|r| {
+                ScalarKalman::new(0.9, 1.0, 0.1, 0.5, Some(r as Float), Some(1.0), None).unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn rows_match_sequential_filtering() {
+        let zs = array![
+            [1.0, 2.0, Float::NAN, 0.5],
+            [0.0, -1.0, 0.3, 0.2],
+            [3.0, 2.0, 1.0, 0.0]
+        ];
+        let mut rows = filters();
+        let out = filter_rows(&mut rows, &zs, MissingPolicy::Predict).unwrap();
+        for (r, mut filter) in filters().into_iter().enumerate() {
+            let z: Vec<Float> = zs.row(r).to_vec();
+            let expected = filter
+                .filter_series(&z, None, MissingPolicy::Predict)
+                .unwrap();
+            assert_eq!(out.row(r).to_vec(), expected, "row {r}");
+            assert_eq!((rows[r].x(), rows[r].P()), (filter.x(), filter.P()));
+        }
+    }
+
+    #[test]
+    fn failure_reports_row() {
+        let zs = array![[1.0, 2.0], [0.0, Float::INFINITY], [3.0, 2.0]];
+        let error = filter_rows(&mut filters(), &zs, MissingPolicy::Predict).unwrap_err();
+        assert!(matches!(
+            error,
+            KalmanError::InRow { index: 1, source }
+                if matches!(*source, KalmanError::AtStep { index: 1, .. })
+        ));
+    }
+}
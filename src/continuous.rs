@@ -0,0 +1,202 @@
+#[cfg(feature = "python")]
+use numpy::{PyArray1, PyReadonlyArray1};
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+use crate::{
+    check_len, check_variance, Float, Innovation, KalmanError, KalmanResult, ScalarKalman,
+};
+
+/// Scalar Kalman filter for the continuous-time model `dx = drift * x dt + dW`, where
+/// `W` is a Wiener process with diffusion (variance rate) `diffusion`, observed at
+/// irregular times through `z = H x + v`.
+///
+/// Each prediction discretizes the model exactly over the elapsed interval `dt`.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "python", pyclass)]
+pub struct ContinuousKalman {
+    filter: ScalarKalman,
+    drift: Float,
+    diffusion: Float,
+    /// Time of the last measurement fused by `advance_at`.
+    t: Option<Float>,
+}
+
+impl ContinuousKalman {
+    pub fn new(
+        drift: Float,
+        diffusion: Float,
+        H: Float,
+        R: Float,
+        x0: Option<Float>,
+        P0: Option<Float>,
+    ) -> KalmanResult<Self> {
+        Ok(Self {
+            filter: ScalarKalman::new(1.0, H, 0.0, R, x0, P0, None)?,
+            drift,
+            diffusion: check_variance("diffusion", diffusion)?,
+            t: None,
+        })
+    }
+
+    /// Current state estimate.
+    pub fn x(&self) -> Float {
+        self.filter.x()
+    }
+
+    /// Current state variance.
+    pub fn P(&self) -> Float {
+        self.filter.P()
+    }
+
+    /// Discrete-time `A` and `Q` over an interval of length `dt`.
+    fn discretize(&self, dt: Float) -> (Float, Float) {
+        let A = (self.drift * dt).exp();
+        let Q = if self.drift == 0.0 {
+            self.diffusion * dt
+        } else {
+            self.diffusion * (2.0 * self.drift * dt).exp_m1() / (2.0 * self.drift)
+        };
+        (A, Q)
+    }
+
+    pub fn predict(&mut self, dt: Float) -> KalmanResult<()> {
+        if dt.is_nan() || dt < 0.0 {
+            return Err(KalmanError::InvalidParameter {
+                name: "dt",
+                value: dt,
+            });
+        }
+        let (A, Q) = self.discretize(dt);
+        self.filter.A = A;
+        self.filter.Q = Q;
+        self.filter.predict();
+        if let Some(t) = &mut self.t {
+            *t += dt;
+        }
+        Ok(())
+    }
+
+    pub fn update(&mut self, z: Float) -> KalmanResult<Innovation> {
+        self.filter.update(z)
+    }
+
+    pub fn advance(&mut self, z: Float, dt: Float) -> KalmanResult<Float> {
+        self.predict(dt)?;
+        self.update(z)?;
+        Ok(self.filter.x)
+    }
+
+    /// Advance to a measurement taken at time `t`. The first measurement is fused
+    /// without any prediction.
+    pub fn advance_at(&mut self, z: Float, t: Float) -> KalmanResult<Float> {
+        let dt = self.t.map_or(0.0, |last| t - last);
+        self.advance(z, dt)?;
+        self.t = Some(t);
+        Ok(self.filter.x)
+    }
+
+    /// Filter measurements `zs` taken at the non-decreasing times `ts`.
+    pub fn filter_timed(&mut self, zs: &[Float], ts: &[Float]) -> KalmanResult<Vec<Float>> {
+        check_len("t", ts.len(), zs.len())?;
+        zs.iter()
+            .zip(ts)
+            .enumerate()
+            .map(|(k, (&z, &t))| self.advance_at(z, t).map_err(|e| e.at_step(k)))
+            .collect()
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl ContinuousKalman {
+    #[new]
+    fn py_new(
+        drift: Float,
+        diffusion: Float,
+        H: Float,
+        R: Float,
+        x0: Option<Float>,
+        P0: Option<Float>,
+    ) -> PyResult<Self> {
+        Ok(Self::new(drift, diffusion, H, R, x0, P0)?)
+    }
+    /// Current state estimate.
+    #[getter(x)]
+    fn get_x(&self) -> Float {
+        self.x()
+    }
+    /// Current state variance.
+    #[getter(P)]
+    fn get_P(&self) -> Float {
+        self.P()
+    }
+    /// Propagate the state over an interval `dt` without a measurement.
+    #[pyo3(name = "predict")]
+    fn py_predict(&mut self, dt: Float) -> PyResult<()> {
+        Ok(self.predict(dt)?)
+    }
+    /// Fuse measurement `z`, returning the innovation `y` and gain `K` as `(y, K)`.
+    #[pyo3(name = "update")]
+    fn py_update(&mut self, z: Float) -> PyResult<(Float, Float)> {
+        let Innovation { y, K, .. } = self.update(z)?;
+        Ok((y, K))
+    }
+    #[pyo3(name = "advance")]
+    fn py_advance(&mut self, z: Float, dt: Float) -> PyResult<Float> {
+        Ok(self.advance(z, dt)?)
+    }
+}
+
+/// Filter a measurement series `v` taken at the non-decreasing times `t`.
+#[cfg(feature = "python")]
+#[pyfunction]
+pub(crate) fn kfilter_timed<'py>(
+    py: Python<'py>,
+    filter: &mut ContinuousKalman,
+    v: PyReadonlyArray1<Float>,
+    t: PyReadonlyArray1<Float>,
+) -> PyResult<&'py PyArray1<Float>> {
+    let zs: Vec<Float> = v.as_array().iter().copied().collect();
+    let ts: Vec<Float> = t.as_array().iter().copied().collect();
+    Ok(PyArray1::from_vec(py, filter.filter_timed(&zs, &ts)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prediction_composes_over_intervals() {
+        let filter = ContinuousKalman::new(-0.7, 0.3, 1.0, 0.5, Some(1.2), Some(0.4)).unwrap();
+        let (mut once, mut twice) = (filter.clone(), filter);
+        once.predict(2.0).unwrap();
+        twice.predict(1.0).unwrap();
+        twice.predict(1.0).unwrap();
+        assert!((once.x() - twice.x()).abs() < 1e-15);
+        assert!((once.P() - twice.P()).abs() < 1e-15);
+    }
+
+    #[test]
+    fn driftless_model_is_a_random_walk() {
+        let filter = ContinuousKalman::new(0.0, 0.3, 1.0, 0.5, None, None).unwrap();
+        assert_eq!(filter.discretize(2.5), (1.0, 0.3 * 2.5));
+    }
+
+    #[test]
+    fn rejects_decreasing_timestamps() {
+        let mut filter = ContinuousKalman::new(-0.7, 0.3, 1.0, 0.5, None, Some(1.0)).unwrap();
+        filter.advance_at(0.1, 1.0).unwrap();
+        filter.advance_at(0.2, 2.0).unwrap();
+        assert!(matches!(
+            filter.clone().advance_at(0.3, 1.5),
+            Err(KalmanError::InvalidParameter { name: "dt", .. })
+        ));
+        let error = filter.filter_timed(&[0.3, 0.4], &[3.0, 2.5]).unwrap_err();
+        assert!(matches!(
+            error,
+            KalmanError::AtStep { index: 1, source }
+                if matches!(*source, KalmanError::InvalidParameter { name: "dt", .. })
+        ));
+    }
+}
@@ -0,0 +1,237 @@
+use ndarray::{Array1, Array2, ArrayView1};
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray1, PyArray2, PyReadonlyArray1, PyReadonlyArray2};
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+use crate::linalg::invert;
+use crate::multivariate::{check_shape, check_vec_len};
+use crate::{Float, KalmanError, KalmanResult};
+
+/// A vector-valued model function, such as the state transition `f` or measurement `h`.
+pub type VectorFn = Box<dyn Fn(ArrayView1<Float>) -> KalmanResult<Array1<Float>> + Send + Sync>;
+/// The Jacobian of a [`VectorFn`], evaluated at a state.
+pub type JacobianFn = Box<dyn Fn(ArrayView1<Float>) -> KalmanResult<Array2<Float>> + Send + Sync>;
+
+/// Extended Kalman filter with nonlinear state transition `f` and measurement `h`,
+/// linearized through their Jacobians `F` and `H` at every step.
+#[cfg_attr(feature = "python", pyclass)]
+pub struct ExtendedKalman {
+    x: Array1<Float>,
+    P: Array2<Float>,
+    Q: Array2<Float>,
+    R: Array2<Float>,
+    f: VectorFn,
+    F: JacobianFn,
+    h: VectorFn,
+    H: JacobianFn,
+}
+
+fn check_jacobian(name: &'static str, J: &Array2<Float>) -> KalmanResult<()> {
+    if J.iter().any(|v| !v.is_finite()) {
+        return Err(KalmanError::NonFiniteJacobian { name });
+    }
+    Ok(())
+}
+
+impl ExtendedKalman {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        f: VectorFn,
+        F: JacobianFn,
+        h: VectorFn,
+        H: JacobianFn,
+        Q: Array2<Float>,
+        R: Array2<Float>,
+        x0: Option<Array1<Float>>,
+        P0: Option<Array2<Float>>,
+    ) -> KalmanResult<Self> {
+        let n = Q.nrows();
+        let m = R.nrows();
+        check_shape("Q", &Q, n, n)?;
+        check_shape("R", &R, m, m)?;
+        let x = x0.unwrap_or_else(|| Array1::zeros(n));
+        check_vec_len("x0", &x.view(), n)?;
+        let P = P0.unwrap_or_else(|| Array2::zeros((n, n)));
+        check_shape("P0", &P, n, n)?;
+        Ok(Self {
+            x,
+            P,
+            Q,
+            R,
+            f,
+            F,
+            h,
+            H,
+        })
+    }
+
+    /// Current state estimate.
+    pub fn x(&self) -> &Array1<Float> {
+        &self.x
+    }
+
+    /// Current state covariance.
+    pub fn P(&self) -> &Array2<Float> {
+        &self.P
+    }
+
+    pub fn predict(&mut self) -> KalmanResult<()> {
+        let n = self.x.len();
+        let F = (self.F)(self.x.view())?;
+        check_shape("F", &F, n, n)?;
+        check_jacobian("F", &F)?;
+        let x = (self.f)(self.x.view())?;
+        check_vec_len("f(x)", &x.view(), n)?;
+        self.x = x;
+        self.P = F.dot(&self.P).dot(&F.t()) + &self.Q;
+        Ok(())
+    }
+
+    pub fn update(&mut self, z: ArrayView1<Float>) -> KalmanResult<()> {
+        let (n, m) = (self.x.len(), self.R.nrows());
+        check_vec_len("z", &z, m)?;
+        let H = (self.H)(self.x.view())?;
+        check_shape("H", &H, m, n)?;
+        check_jacobian("H", &H)?;
+        let hx = (self.h)(self.x.view())?;
+        check_vec_len("h(x)", &hx.view(), m)?;
+        let y = &z - &hx;
+        let PHt = self.P.dot(&H.t());
+        let S = H.dot(&PHt) + &self.R;
+        let S_inv = invert(&S).ok_or(KalmanError::FailedMatrixInverse {
+            matrix_name: "Innovation (measurement pre-fit residual `S`)",
+        })?;
+        let K = PHt.dot(&S_inv);
+        self.x += &K.dot(&y);
+        let I_KH = Array2::<Float>::eye(n) - K.dot(&H);
+        self.P = I_KH.dot(&self.P);
+        Ok(())
+    }
+
+    pub fn advance(&mut self, z: ArrayView1<Float>) -> KalmanResult<&Array1<Float>> {
+        self.predict()?;
+        self.update(z)?;
+        Ok(&self.x)
+    }
+}
+
+#[cfg(feature = "python")]
+/// Call a Python model function on a state, converting any exception to a `KalmanError`.
+fn call_py(name: &'static str, func: &PyObject, x: ArrayView1<Float>) -> KalmanResult<PyObject> {
+    Python::with_gil(|py| {
+        let x = x.to_owned().into_pyarray(py);
+        func.call1(py, (x,))
+            .map_err(|e| KalmanError::CallbackFailed {
+                name,
+                message: e.to_string(),
+            })
+    })
+}
+
+#[cfg(feature = "python")]
+pub(crate) fn py_vector_fn(name: &'static str, func: PyObject) -> VectorFn {
+    Box::new(move |x| {
+        let out = call_py(name, &func, x)?;
+        Python::with_gil(|py| {
+            out.extract::<Vec<Float>>(py)
+                .map(Array1::from)
+                .map_err(|e| KalmanError::CallbackFailed {
+                    name,
+                    message: e.to_string(),
+                })
+        })
+    })
+}
+
+#[cfg(feature = "python")]
+fn py_jacobian_fn(name: &'static str, func: PyObject) -> JacobianFn {
+    Box::new(move |x| {
+        let out = call_py(name, &func, x)?;
+        Python::with_gil(|py| {
+            let out = out.as_ref(py);
+            if let Ok(J) = out.extract::<PyReadonlyArray2<Float>>() {
+                return Ok(J.to_owned_array());
+            }
+            let rows =
+                out.extract::<Vec<Vec<Float>>>()
+                    .map_err(|e| KalmanError::CallbackFailed {
+                        name,
+                        message: e.to_string(),
+                    })?;
+            let cols = rows.first().map_or(0, Vec::len);
+            if rows.iter().any(|row| row.len() != cols) {
+                return Err(KalmanError::CallbackFailed {
+                    name,
+                    message: "ragged nested sequence".to_owned(),
+                });
+            }
+            let flat: Vec<Float> = rows.iter().flatten().copied().collect();
+            Ok(Array2::from_shape_vec((rows.len(), cols), flat)
+                .expect("every row has `cols` entries"))
+        })
+    })
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl ExtendedKalman {
+    #[new]
+    #[allow(clippy::too_many_arguments)]
+    fn py_new(
+        f: PyObject,
+        F: PyObject,
+        h: PyObject,
+        H: PyObject,
+        Q: PyReadonlyArray2<Float>,
+        R: PyReadonlyArray2<Float>,
+        x0: Option<PyReadonlyArray1<Float>>,
+        P0: Option<PyReadonlyArray2<Float>>,
+    ) -> PyResult<Self> {
+        Ok(Self::new(
+            py_vector_fn("f", f),
+            py_jacobian_fn("F", F),
+            py_vector_fn("h", h),
+            py_jacobian_fn("H", H),
+            Q.to_owned_array(),
+            R.to_owned_array(),
+            x0.map(|x0| x0.to_owned_array()),
+            P0.map(|P0| P0.to_owned_array()),
+        )?)
+    }
+    /// Current state estimate.
+    #[getter(x)]
+    fn get_x<'py>(&self, py: Python<'py>) -> &'py PyArray1<Float> {
+        self.x.clone().into_pyarray(py)
+    }
+    /// Current state covariance.
+    #[getter(P)]
+    fn get_P<'py>(&self, py: Python<'py>) -> &'py PyArray2<Float> {
+        self.P.clone().into_pyarray(py)
+    }
+    #[pyo3(name = "advance")]
+    fn py_advance<'py>(
+        &mut self,
+        py: Python<'py>,
+        z: PyReadonlyArray1<Float>,
+    ) -> PyResult<&'py PyArray1<Float>> {
+        Ok(self.advance(z.as_array())?.clone().into_pyarray(py))
+    }
+}
+
+/// Filter a series of measurement vectors with an extended Kalman filter, one per row
+/// of `v`, returning one state estimate per row.
+#[cfg(feature = "python")]
+#[pyfunction]
+pub(crate) fn kfilter_ekf<'py>(
+    py: Python<'py>,
+    filter: &mut ExtendedKalman,
+    v: PyReadonlyArray2<Float>,
+) -> PyResult<&'py PyArray2<Float>> {
+    let v = v.as_array();
+    let mut out = Array2::zeros((v.nrows(), filter.x.len()));
+    for (z, mut row) in v.outer_iter().zip(out.outer_iter_mut()) {
+        row.assign(filter.advance(z)?);
+    }
+    Ok(out.into_pyarray(py))
+}
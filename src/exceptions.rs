@@ -0,0 +1,110 @@
+//! Python exceptions raised for a [`KalmanError`](crate::KalmanError).
+//!
+//! Every exception derives from `kalman_no_control.KalmanError`, itself a `ValueError`,
+//! and carries the attributes `row` (index of the failing series of a batch, or
+//! `None`), `step` (index of the failing step of a series, or `None`), `name` (the
+//! offending quantity, or `None`) and `value` (its value, or `None`).
+// `create_exception!` in PyO3 0.16 tests a `cfg` that newer compilers do not know.
+#![allow(unexpected_cfgs)]
+
+use pyo3::create_exception;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use crate::KalmanError as Error;
+
+create_exception!(
+    kalman_no_control,
+    KalmanError,
+    PyValueError,
+    "Base class of all errors raised by a filter."
+);
+create_exception!(
+    kalman_no_control,
+    SingularInnovationError,
+    KalmanError,
+    "An innovation (or other) variance or matrix could not be inverted."
+);
+create_exception!(
+    kalman_no_control,
+    InvalidParameterError,
+    KalmanError,
+    "A model parameter, option or input has an invalid value or shape."
+);
+create_exception!(
+    kalman_no_control,
+    DivergenceError,
+    KalmanError,
+    "The filter state became non-finite or its variance negative."
+);
+
+/// Register the exception classes in the Python module.
+pub(crate) fn register(py: Python, m: &PyModule) -> PyResult<()> {
+    m.add("KalmanError", py.get_type::<KalmanError>())?;
+    m.add(
+        "SingularInnovationError",
+        py.get_type::<SingularInnovationError>(),
+    )?;
+    m.add(
+        "InvalidParameterError",
+        py.get_type::<InvalidParameterError>(),
+    )?;
+    m.add("DivergenceError", py.get_type::<DivergenceError>())?;
+    Ok(())
+}
+
+/// Create the exception for `err` with its structured attributes set.
+fn to_pyerr(py: Python, err: Error) -> PyResult<PyErr> {
+    let message = err.to_string();
+    let (row, err) = match err {
+        Error::InRow { index, source } => (Some(index), *source),
+        err => (None, err),
+    };
+    let (step, err) = match err {
+        Error::AtStep { index, source } => (Some(index), *source),
+        Error::MissingMeasurement { index } => (Some(index), err),
+        err => (None, err),
+    };
+    let (name, value): (Option<&str>, PyObject) = match &err {
+        Error::FailedScalarInverse { scalar_name: name }
+        | Error::FailedMatrixInverse { matrix_name: name }
+        | Error::NotPositiveSemidefinite { matrix_name: name }
+        | Error::NonFiniteJacobian { name }
+        | Error::CallbackFailed { name, .. } => (Some(name), py.None()),
+        Error::DimensionMismatch { name, found, .. } => (Some(name), found.to_object(py)),
+        Error::InvalidParameter { name, value }
+        | Error::NonFiniteInput { name, value }
+        | Error::NegativeVariance { name, value }
+        | Error::Diverged { name, value } => (Some(name), value.to_object(py)),
+        Error::InvalidOption { name, value } => (Some(name), value.to_object(py)),
+        Error::MissingMeasurement { .. }
+        | Error::InsufficientData { .. }
+        | Error::AtStep { .. }
+        | Error::InRow { .. } => (None, py.None()),
+    };
+    let pyerr = match err {
+        Error::FailedScalarInverse { .. }
+        | Error::FailedMatrixInverse { .. }
+        | Error::NotPositiveSemidefinite { .. } => SingularInnovationError::new_err(message),
+        Error::DimensionMismatch { .. }
+        | Error::InvalidParameter { .. }
+        | Error::InvalidOption { .. }
+        | Error::NonFiniteInput { .. } => InvalidParameterError::new_err(message),
+        Error::NegativeVariance { .. }
+        | Error::Diverged { .. }
+        | Error::NonFiniteJacobian { .. } => DivergenceError::new_err(message),
+        _ => KalmanError::new_err(message),
+    };
+    let exc = pyerr.value(py);
+    exc.setattr("row", row)?;
+    exc.setattr("step", step)?;
+    exc.setattr("name", name)?;
+    exc.setattr("value", value)?;
+    Ok(pyerr)
+}
+
+impl From<Error> for PyErr {
+    fn from(err: Error) -> Self {
+        Python::with_gil(|py| to_pyerr(py, err).unwrap_or_else(|e| e))
+    }
+}
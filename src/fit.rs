@@ -0,0 +1,193 @@
+#[cfg(feature = "python")]
+use numpy::PyArray1;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::types::PyDict;
+
+use crate::smoother::Smoothed;
+use crate::{
+    check_variance, FilterFloat, Float, KalmanError, KalmanResult, MissingPolicy, ScalarFilter,
+    ScalarKalman, SqrtKalman,
+};
+#[cfg(feature = "python")]
+use crate::{read_series, with_scalar_filter};
+
+/// Convergence diagnostics of an EM fit.
+#[derive(Debug, Clone)]
+pub struct FitReport<T = Float> {
+    pub iterations: usize,
+    pub converged: bool,
+    /// Log-likelihood of the series under the parameters at the start of each iteration.
+    pub loglik: Vec<T>,
+}
+
+impl<T: FilterFloat> ScalarKalman<T> {
+    /// Estimate `A`, `Q`, `R` (and `H` if `fit_H`) from `zs` by expectation-maximization.
+    ///
+    /// The E-step runs the RTS smoother from this filter's current state, which is also
+    /// used as the initial state of the returned filter. Iteration stops once the
+    /// log-likelihood improves by less than `tol`. `H` is held fixed by default since it
+    /// is only identifiable up to the scale of the state.
+    pub fn fit_em(
+        &self,
+        zs: &[T],
+        max_iter: usize,
+        tol: T,
+        fit_H: bool,
+    ) -> KalmanResult<(ScalarKalman<T>, FitReport<T>)> {
+        let N = zs.len();
+        if N < 2 {
+            return Err(KalmanError::InsufficientData {
+                needed: 2,
+                found: N,
+            });
+        }
+        if let Some(index) = zs.iter().position(|z| z.is_nan()) {
+            return Err(KalmanError::MissingMeasurement { index });
+        }
+
+        let mut model = self.clone();
+        let mut report = FitReport {
+            iterations: 0,
+            converged: false,
+            loglik: Vec::with_capacity(max_iter),
+        };
+        while report.iterations < max_iter {
+            let ll = model
+                .clone()
+                .log_likelihood(zs, None, MissingPolicy::Raise)?;
+            if let Some(&prev) = report.loglik.last() {
+                if (ll - prev).abs() < tol {
+                    report.loglik.push(ll);
+                    report.converged = true;
+                    break;
+                }
+            }
+            report.loglik.push(ll);
+            report.iterations += 1;
+
+            let Smoothed { x, P, P_lag } = model.clone().smooth(zs)?;
+            // Second moments E[x_k^2] and E[x_k x_{k-1}] under the smoothed distribution.
+            let S11: Vec<T> = x.iter().zip(&P).map(|(&x, &P)| P + x * x).collect();
+            let S10: Vec<T> = (1..N).map(|k| P_lag[k] + x[k] * x[k - 1]).collect();
+
+            let S00_sum: T = S11[..N - 1].iter().copied().sum();
+            let S10_sum: T = S10.iter().copied().sum();
+            let S11_sum: T = S11[1..].iter().copied().sum();
+            model.A = S10_sum / S00_sum;
+            let Q = (S11_sum - model.A * S10_sum) / T::of((N - 1) as f64);
+            model.Q = check_variance("Q", Q)?;
+
+            if fit_H {
+                let zx: T = zs.iter().zip(&x).map(|(&z, &x)| z * x).sum();
+                model.H = zx / S11.iter().copied().sum();
+            }
+            let H = model.H;
+            let R = zs
+                .iter()
+                .zip(&x)
+                .zip(&S11)
+                .map(|((&z, &x), &S11)| z * z - T::of(2.0) * H * z * x + H * H * S11)
+                .sum::<T>()
+                / T::of(N as f64);
+            model.R = check_variance("R", R)?;
+        }
+        Ok((model, report))
+    }
+}
+
+impl SqrtKalman {
+    /// Estimate the parameters from `zs` as [`ScalarKalman::fit_em`].
+    ///
+    /// The E-step runs in variance form; only the returned filter is in square-root form.
+    pub fn fit_em(
+        &self,
+        zs: &[Float],
+        max_iter: usize,
+        tol: Float,
+        fit_H: bool,
+    ) -> KalmanResult<(SqrtKalman, FitReport)> {
+        let (model, report) = self.model().fit_em(zs, max_iter, tol, fit_H)?;
+        Ok((model.into(), report))
+    }
+}
+
+/// Fit the parameters of `filter` to a measurement series by expectation-maximization.
+///
+/// Returns the fitted filter, starting from the same state as `filter`, together with a
+/// dict of diagnostics: `iterations`, `converged` and the per-iteration `loglik` array.
+#[cfg(feature = "python")]
+#[pyfunction]
+pub(crate) fn kfit(
+    py: Python,
+    filter: &PyAny,
+    v: &PyAny,
+    max_iter: Option<usize>,
+    tol: Option<Float>,
+    fit_H: Option<bool>,
+) -> PyResult<(PyObject, PyObject)> {
+    with_scalar_filter!(filter, |filter: Class| {
+        let (fitted, report) = filter.fit_em(
+            &read_series(v)?,
+            max_iter.unwrap_or(100),
+            FilterFloat::of(tol.unwrap_or(1e-6)),
+            fit_H.unwrap_or(false),
+        )?;
+        let diagnostics = PyDict::new(py);
+        diagnostics.set_item("iterations", report.iterations)?;
+        diagnostics.set_item("converged", report.converged)?;
+        diagnostics.set_item("loglik", PyArray1::from_vec(py, report.loglik))?;
+        Ok((Class::from(fitted).into_py(py), diagnostics.into_py(py)))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Standard normal samples from a xorshift generator and the Box-Muller transform.
+    fn normals(seed: u64, n: usize) -> Vec<Float> {
+        let mut state = seed;
+        let mut uniform = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state >> 11) as Float / (1u64 << 53) as Float
+        };
+        (0..n)
+            .map(|_| {
+                let (u, v) = (1.0 - uniform(), uniform());
+                (-2.0 * u.ln()).sqrt() * (2.0 * std::f64::consts::PI * v).cos()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn em_recovers_parameters_with_monotone_likelihood() {
+        let (A, Q, R): (Float, Float, Float) = (0.9, 0.09, 0.25);
+        let N = 5000;
+        let w = normals(1, N);
+        let v = normals(2, N);
+        let mut x = 0.0;
+        let zs: Vec<Float> = (0..N)
+            .map(|k| {
+                x = A * x + Q.sqrt() * w[k];
+                x + R.sqrt() * v[k]
+            })
+            .collect();
+
+        let start = ScalarKalman::new(0.5, 1.0, 1.0, 1.0, Some(0.0), Some(1.0), None).unwrap();
+        let (fitted, report) = start.fit_em(&zs, 500, 1e-8, false).unwrap();
+        assert!(report.converged);
+        for pair in report.loglik.windows(2) {
+            assert!(
+                pair[1] >= pair[0] - 1e-6,
+                "log-likelihood decreased: {pair:?}"
+            );
+        }
+        assert!((fitted.A - A).abs() < 0.03, "A = {}", fitted.A);
+        assert!((fitted.Q - Q).abs() < 0.03, "Q = {}", fitted.Q);
+        assert!((fitted.R - R).abs() < 0.03, "R = {}", fitted.R);
+    }
+}
@@ -0,0 +1,134 @@
+use crate::{
+    FilterFloat, Float, KalmanError, KalmanResult, ScalarFilter, ScalarKalman, SqrtKalman,
+};
+
+/// Quantile function of the standard normal distribution, using Acklam's rational
+/// approximation (relative error below 1.2e-9).
+fn normal_quantile(p: Float) -> Float {
+    const A: [Float; 6] = [
+        -3.969683028665376e1,
+        2.209460984245205e2,
+        -2.759285104469687e2,
+        1.38357751867269e2,
+        -3.066479806614716e1,
+        2.506628277459239,
+    ];
+    const B: [Float; 5] = [
+        -5.447609879822406e1,
+        1.615858368580409e2,
+        -1.556989798598866e2,
+        6.680131188771972e1,
+        -1.328068155288572e1,
+    ];
+    const C: [Float; 6] = [
+        -7.784894002430293e-3,
+        -3.223964580411365e-1,
+        -2.400758277161838,
+        -2.549732539343734,
+        4.374664141464968,
+        2.938163982698783,
+    ];
+    const D: [Float; 4] = [
+        7.784695709041462e-3,
+        3.224671290700398e-1,
+        2.445134137142996,
+        3.754408661907416,
+    ];
+    const P_LOW: Float = 0.02425;
+
+    let tail = |q: Float| {
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+    if p < P_LOW {
+        tail((-2.0 * p.ln()).sqrt())
+    } else if p > 1.0 - P_LOW {
+        -tail((-2.0 * (1.0 - p).ln()).sqrt())
+    } else {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    }
+}
+
+/// Predicted state means and variances for the next steps.
+#[derive(Debug, Clone)]
+pub struct Forecast<T = Float> {
+    pub x: Vec<T>,
+    pub P: Vec<T>,
+}
+
+impl<T: FilterFloat> Forecast<T> {
+    /// Central prediction interval covering probability `level`, as `(lower, upper)`.
+    pub fn interval(&self, level: Float) -> KalmanResult<(Vec<T>, Vec<T>)> {
+        if !(level > 0.0 && level < 1.0) {
+            return Err(KalmanError::InvalidParameter {
+                name: "level",
+                value: level,
+            });
+        }
+        let z = T::of(normal_quantile(0.5 + level / 2.0));
+        let half: Vec<T> = self.P.iter().map(|P| z * P.sqrt()).collect();
+        let lower = self.x.iter().zip(&half).map(|(&x, &h)| x - h).collect();
+        let upper = self.x.iter().zip(&half).map(|(&x, &h)| x + h).collect();
+        Ok((lower, upper))
+    }
+}
+
+impl<T: FilterFloat> ScalarKalman<T> {
+    /// Propagate the current state `n` steps ahead without control input, leaving the
+    /// filter untouched.
+    pub fn forecast(&self, n: usize) -> Forecast<T> {
+        forecast(self.clone(), n)
+    }
+}
+
+impl SqrtKalman {
+    /// Propagate the current state `n` steps ahead, as [`ScalarKalman::forecast`].
+    pub fn forecast(&self, n: usize) -> Forecast {
+        forecast(self.clone(), n)
+    }
+}
+
+fn forecast<T: FilterFloat>(mut filter: impl ScalarFilter<T>, n: usize) -> Forecast<T> {
+    let mut out = Forecast {
+        x: Vec::with_capacity(n),
+        P: Vec::with_capacity(n),
+    };
+    for _ in 0..n {
+        filter.predict_controlled(T::zero());
+        out.x.push(filter.model().x);
+        out.P.push(filter.model().P);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normal_quantile_matches_known_values() {
+        // The central region, then the lower and upper tails.
+        let cases = [
+            (0.5, 0.0),
+            (0.975, 1.959963984540054),
+            (0.01, -2.326347874040841),
+            (0.999, 3.090232306167814),
+        ];
+        for (p, expected) in cases {
+            let q = normal_quantile(p);
+            assert!((q - expected).abs() <= 1e-8, "quantile({p}) = {q}");
+        }
+    }
+
+    #[test]
+    fn forecast_leaves_filter_untouched() {
+        let filter = ScalarKalman::new(0.9, 1.0, 0.1, 0.5, Some(2.0), Some(1.0), None).unwrap();
+        let forecast = filter.forecast(3);
+        assert_eq!((filter.x(), filter.P()), (2.0, 1.0));
+        assert_eq!(forecast.x, [1.8, 0.9 * 1.8, 0.9 * 0.9 * 1.8]);
+        assert_eq!(forecast.P[0], 0.9 * 1.0 * 0.9 + 0.1);
+    }
+}
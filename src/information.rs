@@ -0,0 +1,309 @@
+#[cfg(feature = "python")]
+use numpy::PyReadonlyArray1;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+#[cfg(feature = "python")]
+use crate::PyScalarKalman;
+use crate::{check_finite, check_variance, Float, KalmanError, KalmanResult, ScalarKalman};
+
+/// Information form of [`ScalarKalman`], tracking the information `Y = 1 / P` and the
+/// information state `y = x / P` instead of the state and its variance.
+///
+/// A filter can start from zero information, i.e. a completely unknown prior, and
+/// measurements fuse by plain addition.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "python", pyclass(module = "kalman_no_control"))]
+pub struct InformationKalman {
+    y: Float,
+    Y: Float,
+    A: Float,
+    H: Float,
+    Q: Float,
+    R: Float,
+    B: Float,
+}
+
+impl InformationKalman {
+    /// Create a filter with information state `y0` and information `Y0` (both default
+    /// to zero, an uninformative prior) and control gain `B` (defaults to zero).
+    ///
+    /// Fails if `Q` or `Y0` is negative or `R` is not positive.
+    pub fn new(
+        A: Float,
+        H: Float,
+        Q: Float,
+        R: Float,
+        y0: Option<Float>,
+        Y0: Option<Float>,
+        B: Option<Float>,
+    ) -> KalmanResult<Self> {
+        if R.is_nan() || R <= 0.0 {
+            return Err(KalmanError::InvalidParameter {
+                name: "R",
+                value: R,
+            });
+        }
+        Ok(Self {
+            y: y0.unwrap_or(0.0),
+            Y: check_variance("Y0", Y0.unwrap_or(0.0))?,
+            A,
+            H,
+            Q: check_variance("Q", Q)?,
+            R,
+            B: B.unwrap_or(0.0),
+        })
+    }
+
+    /// Current information state `x / P`.
+    pub fn y(&self) -> Float {
+        self.y
+    }
+
+    /// Current information `1 / P`.
+    pub fn Y(&self) -> Float {
+        self.Y
+    }
+
+    /// Current state estimate; NaN while there is no information.
+    pub fn x(&self) -> Float {
+        self.y / self.Y
+    }
+
+    /// Current state variance; infinite while there is no information.
+    pub fn P(&self) -> Float {
+        1.0 / self.Y
+    }
+
+    /// Propagate the state one step without control input.
+    pub fn predict(&mut self) -> KalmanResult<()> {
+        self.predict_controlled(0.0)
+    }
+
+    /// Propagate the state with control input `u` applied through the gain `B`.
+    pub fn predict_controlled(&mut self, u: Float) -> KalmanResult<()> {
+        if self.A == 0.0 {
+            // The prior is forgotten entirely; only the process noise remains.
+            if self.Q == 0.0 {
+                return Err(KalmanError::FailedScalarInverse {
+                    scalar_name: "Process noise `Q`",
+                });
+            }
+            self.Y = 1.0 / self.Q;
+            self.y = self.Y * self.B * u;
+            return Ok(());
+        }
+        // Information of `A x`, then of `A x + w`.
+        let M = self.Y / (self.A * self.A);
+        let Y = M / (1.0 + self.Q * M);
+        let y = self.y / (self.A * (1.0 + self.Q * M)) + Y * self.B * u;
+        self.y = check_finite("y", y)?;
+        self.Y = check_finite("Y", Y)?;
+        Ok(())
+    }
+
+    /// Fuse measurement `z` into the state.
+    pub fn update(&mut self, z: Float) -> KalmanResult<()> {
+        self.update_many(std::slice::from_ref(&z))
+    }
+
+    /// Fuse several measurements of the current state at once by adding their
+    /// information.
+    pub fn update_many(&mut self, zs: &[Float]) -> KalmanResult<()> {
+        if let Some(&z) = zs.iter().find(|z| !z.is_finite()) {
+            return Err(KalmanError::NonFiniteInput {
+                name: "z",
+                value: z,
+            });
+        }
+        let sum: Float = zs.iter().sum();
+        self.y += self.H * sum / self.R;
+        self.Y += zs.len() as Float * self.H * self.H / self.R;
+        Ok(())
+    }
+
+    /// Predict and then update with `z`, returning the new state estimate.
+    pub fn advance(&mut self, z: Float) -> KalmanResult<Float> {
+        self.advance_controlled(z, 0.0)
+    }
+
+    /// Like `advance`, with control input `u`.
+    pub fn advance_controlled(&mut self, z: Float, u: Float) -> KalmanResult<Float> {
+        self.predict_controlled(u)?;
+        self.update(z)?;
+        Ok(self.x())
+    }
+}
+
+impl TryFrom<&ScalarKalman> for InformationKalman {
+    type Error = KalmanError;
+
+    /// Fails if the filter's variance is zero, i.e. its information infinite.
+    ///
+    /// The filter's tolerance and update form have no counterpart in information form
+    /// and are dropped.
+    fn try_from(filter: &ScalarKalman) -> KalmanResult<Self> {
+        if filter.P == 0.0 {
+            return Err(KalmanError::FailedScalarInverse {
+                scalar_name: "State variance `P`",
+            });
+        }
+        let Y = 1.0 / filter.P;
+        Self::new(
+            filter.A,
+            filter.H,
+            filter.Q,
+            filter.R,
+            Some(filter.x * Y),
+            Some(Y),
+            Some(filter.B),
+        )
+    }
+}
+
+impl TryFrom<&InformationKalman> for ScalarKalman {
+    type Error = KalmanError;
+
+    /// Fails if the filter has no information, i.e. an infinite variance.
+    ///
+    /// The result has the default tolerance and update form; apply others with
+    /// [`ScalarKalman::with_tolerance`] and [`ScalarKalman::with_update_form`].
+    fn try_from(filter: &InformationKalman) -> KalmanResult<Self> {
+        if filter.Y == 0.0 {
+            return Err(KalmanError::FailedScalarInverse {
+                scalar_name: "Information `Y`",
+            });
+        }
+        ScalarKalman::new(
+            filter.A,
+            filter.H,
+            filter.Q,
+            filter.R,
+            Some(filter.x()),
+            Some(filter.P()),
+            Some(filter.B),
+        )
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl InformationKalman {
+    #[new]
+    fn py_new(
+        A: Float,
+        H: Float,
+        Q: Float,
+        R: Float,
+        y0: Option<Float>,
+        Y0: Option<Float>,
+        B: Option<Float>,
+    ) -> PyResult<Self> {
+        Ok(Self::new(A, H, Q, R, y0, Y0, B)?)
+    }
+    /// Convert a `ScalarKalman`, which must have a non-zero variance.
+    #[staticmethod]
+    fn from_scalar(filter: &PyScalarKalman) -> PyResult<Self> {
+        Ok(Self::try_from(&filter.0)?)
+    }
+    /// Convert to a `ScalarKalman` with the default `tol` and `form`, which requires
+    /// non-zero information.
+    fn to_scalar(&self) -> PyResult<PyScalarKalman> {
+        Ok(PyScalarKalman(ScalarKalman::try_from(self)?))
+    }
+    fn __repr__(&self) -> String {
+        format!(
+            "InformationKalman(A={:?}, H={:?}, Q={:?}, R={:?}, y0={:?}, Y0={:?}, B={:?})",
+            self.A, self.H, self.Q, self.R, self.y, self.Y, self.B
+        )
+    }
+    /// Current state estimate; NaN while there is no information.
+    #[getter(x)]
+    fn get_x(&self) -> Float {
+        self.x()
+    }
+    /// Current state variance; infinite while there is no information.
+    #[getter(P)]
+    fn get_P(&self) -> Float {
+        self.P()
+    }
+    /// Current information state `x / P`.
+    #[getter(y)]
+    fn get_y(&self) -> Float {
+        self.y
+    }
+    /// Current information `1 / P`.
+    #[getter(Y)]
+    fn get_Y(&self) -> Float {
+        self.Y
+    }
+    /// Propagate the state one step without a measurement, with optional control `u`.
+    #[pyo3(name = "predict")]
+    fn py_predict(&mut self, u: Option<Float>) -> PyResult<()> {
+        Ok(self.predict_controlled(u.unwrap_or(0.0))?)
+    }
+    /// Fuse measurement `z`.
+    #[pyo3(name = "update")]
+    fn py_update(&mut self, z: Float) -> PyResult<()> {
+        Ok(self.update(z)?)
+    }
+    /// Fuse an array of measurements of the current state.
+    #[pyo3(name = "update_many")]
+    fn py_update_many(&mut self, v: PyReadonlyArray1<Float>) -> PyResult<()> {
+        let zs: Vec<Float> = v.as_array().iter().copied().collect();
+        Ok(self.update_many(&zs)?)
+    }
+    #[pyo3(name = "advance")]
+    fn py_advance(&mut self, z: Float, u: Option<Float>) -> PyResult<Float> {
+        Ok(self.advance_controlled(z, u.unwrap_or(0.0))?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(what: &str, k: usize, a: Float, b: Float, tol: Float) {
+        assert!(
+            (a - b).abs() <= tol * a.abs().max(b.abs()),
+            "{what} at step {k}: {a} vs {b}"
+        );
+    }
+
+    #[test]
+    fn matches_scalar_filter() {
+        let mut scalar =
+            ScalarKalman::new(0.95, 2.0, 0.1, 0.5, Some(0.3), Some(2.0), Some(0.5)).unwrap();
+        let mut info = InformationKalman::try_from(&scalar).unwrap();
+        for k in 0..50 {
+            let (z, u) = ((0.3 * k as Float).sin(), (0.1 * k as Float).cos());
+            scalar.advance_controlled(z, u).unwrap();
+            info.advance_controlled(z, u).unwrap();
+            assert_close("x", k, scalar.x(), info.x(), 5e-15);
+            assert_close("P", k, scalar.P(), info.P(), 5e-15);
+        }
+    }
+
+    #[test]
+    fn starts_from_zero_information() {
+        let (H, R) = (2.0, 0.5);
+        let mut info = InformationKalman::new(0.9, H, 0.1, R, None, None, None).unwrap();
+        assert!(info.x().is_nan());
+        assert_eq!(info.P(), Float::INFINITY);
+
+        // The first measurement alone determines the state.
+        info.advance(1.5).unwrap();
+        assert_close("x", 0, info.x(), 1.5 / H, 1e-15);
+        assert_close("P", 0, info.P(), R / (H * H), 1e-15);
+
+        // From there on it tracks the equivalent variance-form filter.
+        let mut scalar = ScalarKalman::try_from(&info).unwrap();
+        for k in 1..20 {
+            let z = (0.3 * k as Float).sin();
+            scalar.advance(z).unwrap();
+            info.advance(z).unwrap();
+            assert_close("x", k, scalar.x(), info.x(), 5e-15);
+            assert_close("P", k, scalar.P(), info.P(), 5e-15);
+        }
+    }
+}
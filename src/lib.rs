@@ -1,110 +1,1156 @@
+//! Kalman filters for scalar and vector state-space models.
+//!
+//! The Python bindings for the `kalman_no_control` module are built with the `python`
+//! feature, which is enabled by default.
 #![allow(non_snake_case)]
+// PyO3 0.16 macros expand to `impl` blocks that newer compilers flag as non-local.
+#![allow(non_local_definitions)]
 
+mod batch;
+mod continuous;
+mod ekf;
+#[cfg(feature = "python")]
+mod exceptions;
+mod fit;
+mod forecast;
+mod information;
+mod likelihood;
+mod linalg;
+mod multivariate;
+mod smoother;
+mod sqrt;
+mod steady;
+mod trajectory;
+mod ukf;
+mod varying;
+
+pub use batch::filter_rows;
+pub use continuous::ContinuousKalman;
+pub use ekf::{ExtendedKalman, JacobianFn, VectorFn};
+pub use fit::FitReport;
+pub use forecast::Forecast;
+pub use information::InformationKalman;
+pub use multivariate::Kalman;
+pub use smoother::Smoothed;
+pub use sqrt::SqrtKalman;
+pub use steady::SteadyState;
+pub use trajectory::Trajectory;
+pub use ukf::{SigmaPoints, UnscentedKalman};
+pub use varying::Schedule;
+
+#[cfg(feature = "python")]
 use numpy::{PyArray1, PyReadonlyArray1};
-use pyo3::exceptions::PyValueError;
+#[cfg(feature = "python")]
 use pyo3::prelude::*;
+#[cfg(feature = "python")]
 use pyo3::wrap_pyfunction;
 
 use thiserror::Error;
 
-type Float = f64;
+/// Default floating-point type of the filters. Only [`ScalarKalman`] and the analyses
+/// built on it can also run in `f32`; see [`FilterFloat`].
+pub type Float = f64;
 
+/// Errors raised while constructing or running a filter.
 #[derive(Error, Debug)]
-enum KalmanError {
+pub enum KalmanError {
     #[error("failed to invert scalar {scalar_name} in operation")]
     FailedScalarInverse { scalar_name: &'static str },
+    #[error("failed to invert matrix {matrix_name} in operation")]
+    FailedMatrixInverse { matrix_name: &'static str },
+    #[error("dimension mismatch for {name}: expected {expected:?}, found {found:?}")]
+    DimensionMismatch {
+        name: &'static str,
+        expected: Vec<usize>,
+        found: Vec<usize>,
+    },
+    #[error("missing measurement at step {index}")]
+    MissingMeasurement { index: usize },
+    #[error("Jacobian {name} has non-finite entries")]
+    NonFiniteJacobian { name: &'static str },
+    #[error("model function {name} failed: {message}")]
+    CallbackFailed { name: &'static str, message: String },
+    #[error("matrix {matrix_name} is not positive semidefinite")]
+    NotPositiveSemidefinite { matrix_name: &'static str },
+    #[error("invalid value {value} for parameter {name}")]
+    InvalidParameter { name: &'static str, value: Float },
+    #[error("need at least {needed} measurements, found {found}")]
+    InsufficientData { needed: usize, found: usize },
+    #[error("invalid value {value:?} for option {name}")]
+    InvalidOption { name: &'static str, value: String },
+    #[error("non-finite value {value} for input {name}")]
+    NonFiniteInput { name: &'static str, value: Float },
+    #[error("variance {name} became negative ({value})")]
+    NegativeVariance { name: &'static str, value: Float },
+    #[error("filter diverged: {name} is {value}")]
+    Diverged { name: &'static str, value: Float },
+    #[error("at step {index}: {source}")]
+    AtStep {
+        index: usize,
+        source: Box<KalmanError>,
+    },
+    #[error("in row {index}: {source}")]
+    InRow {
+        index: usize,
+        source: Box<KalmanError>,
+    },
+}
+pub type KalmanResult<T> = std::result::Result<T, KalmanError>;
+
+impl KalmanError {
+    /// Record that the error occurred while processing step `index` of a series,
+    /// unless it already names its step.
+    pub fn at_step(self, index: usize) -> Self {
+        match self {
+            Self::MissingMeasurement { .. } | Self::AtStep { .. } => self,
+            source => Self::AtStep {
+                index,
+                source: Box::new(source),
+            },
+        }
+    }
+
+    /// Record that the error occurred while filtering row `index` of a batch.
+    pub fn in_row(self, index: usize) -> Self {
+        Self::InRow {
+            index,
+            source: Box::new(self),
+        }
+    }
+}
+
+/// Check that a series has the `expected` length.
+fn check_len(name: &'static str, found: usize, expected: usize) -> KalmanResult<()> {
+    if found != expected {
+        return Err(KalmanError::DimensionMismatch {
+            name,
+            expected: vec![expected],
+            found: vec![found],
+        });
+    }
+    Ok(())
+}
+
+/// Check that an optional missing-sample mask matches a series of length `len`.
+fn check_mask(mask: Option<&[bool]>, len: usize) -> KalmanResult<()> {
+    match mask {
+        Some(mask) => check_len("mask", mask.len(), len),
+        None => Ok(()),
+    }
+}
+
+/// Copy an optional missing-sample mask out of NumPy, checking it matches the series.
+#[cfg(feature = "python")]
+fn read_mask(mask: Option<PyReadonlyArray1<bool>>, len: usize) -> KalmanResult<Option<Vec<bool>>> {
+    match mask {
+        Some(mask) => {
+            check_len("mask", mask.len(), len)?;
+            Ok(Some(mask.as_array().iter().copied().collect()))
+        }
+        None => Ok(None),
+    }
+}
+
+/// What to do with a measurement that is NaN or masked out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MissingPolicy {
+    /// Leave the filter untouched and report the current estimate.
+    Skip,
+    /// Propagate the state without a measurement update.
+    Predict,
+    /// Fail with `KalmanError::MissingMeasurement`.
+    Raise,
+}
+
+impl MissingPolicy {
+    /// Whether a missing sample at step `index` still propagates the state; fails under
+    /// [`MissingPolicy::Raise`].
+    pub(crate) fn predicts(self, index: usize) -> KalmanResult<bool> {
+        match self {
+            Self::Skip => Ok(false),
+            Self::Predict => Ok(true),
+            Self::Raise => Err(KalmanError::MissingMeasurement { index }),
+        }
+    }
+}
+
+impl std::str::FromStr for MissingPolicy {
+    type Err = KalmanError;
+
+    fn from_str(s: &str) -> KalmanResult<Self> {
+        match s {
+            "skip" => Ok(Self::Skip),
+            "predict" => Ok(Self::Predict),
+            "raise" => Ok(Self::Raise),
+            _ => Err(KalmanError::InvalidOption {
+                name: "missing",
+                value: s.to_owned(),
+            }),
+        }
+    }
+}
+
+/// How a measurement update computes the posterior state variance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UpdateForm {
+    /// `P (1 - K H)`, the cheapest form, exact only for the optimal gain.
+    #[default]
+    Standard,
+    /// Joseph's `(1 - K H)^2 P + K^2 R`, which stays non-negative under rounding.
+    Joseph,
+}
+
+impl UpdateForm {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Standard => "standard",
+            Self::Joseph => "joseph",
+        }
+    }
+}
+
+impl std::str::FromStr for UpdateForm {
+    type Err = KalmanError;
+
+    fn from_str(s: &str) -> KalmanResult<Self> {
+        match s {
+            "standard" => Ok(Self::Standard),
+            "joseph" => Ok(Self::Joseph),
+            _ => Err(KalmanError::InvalidOption {
+                name: "form",
+                value: s.to_owned(),
+            }),
+        }
+    }
+}
+
+/// Floating-point types a [`ScalarKalman`] can be computed in.
+pub trait FilterFloat:
+    num_traits::Float + Into<Float> + std::iter::Sum + std::fmt::Debug + Send + Sync + 'static
+{
+    /// Convert an `f64` constant to this type.
+    fn of(v: f64) -> Self;
+}
+
+impl FilterFloat for f32 {
+    fn of(v: f64) -> Self {
+        v as f32
+    }
+}
+
+impl FilterFloat for f64 {
+    fn of(v: f64) -> Self {
+        v
+    }
+}
+
+/// Quantities computed by a measurement update.
+#[derive(Debug, Clone, Copy)]
+pub struct Innovation<T = Float> {
+    /// Measurement residual.
+    pub y: T,
+    /// Innovation variance.
+    pub S: T,
+    /// Kalman gain.
+    pub K: T,
 }
-type KalmanResult<T> = std::result::Result<T, KalmanError>;
 
-impl From<KalmanError> for PyErr {
-    fn from(k: KalmanError) -> Self {
-        PyValueError::new_err(k.to_string())
+impl<T: FilterFloat> Innovation<T> {
+    /// Gaussian log-likelihood of the residual `y` under variance `S`.
+    pub fn log_likelihood(&self) -> T {
+        let two_pi = T::of(2.0 * std::f64::consts::PI);
+        T::of(-0.5) * ((two_pi * self.S).ln() + self.y * self.y / self.S)
     }
 }
 
+/// Pickled form of a `ScalarKalman`, in constructor argument order: `A`, `H`, `Q`, `R`,
+/// `x0`, `P0`, `B`, `tol`, `form`.
+#[cfg(feature = "python")]
+type ScalarState<T> = (T, T, T, T, T, T, T, T, String);
+
+/// Default for [`ScalarKalman::with_tolerance`].
+const DEFAULT_TOLERANCE: f64 = 1e-8;
+
+/// Kalman filter for the scalar model `x' = A x + B u + w`, `z = H x + v`, where the
+/// process noise `w` and measurement noise `v` have variances `Q` and `R`.
 #[derive(Debug, Clone)]
-#[pyclass]
-struct ScalarKalman {
-    x: Float,
-    P: Float,
-    A: Float,
-    H: Float,
-    Q: Float,
-    R: Float,
+pub struct ScalarKalman<T = Float> {
+    x: T,
+    P: T,
+    A: T,
+    H: T,
+    Q: T,
+    R: T,
+    B: T,
+    /// Innovation variances below `tol` are singular; variances below `-tol` are negative.
+    tol: T,
+    form: UpdateForm,
+}
+
+/// Reject negative (or NaN) noise variances.
+fn check_variance<T: FilterFloat>(name: &'static str, value: T) -> KalmanResult<T> {
+    if value.is_nan() || value < T::zero() {
+        return Err(KalmanError::InvalidParameter {
+            name,
+            value: value.into(),
+        });
+    }
+    Ok(value)
+}
+
+/// Fail with `KalmanError::Diverged` if a state quantity has become infinite or NaN.
+fn check_finite<T: FilterFloat>(name: &'static str, value: T) -> KalmanResult<T> {
+    if !value.is_finite() {
+        return Err(KalmanError::Diverged {
+            name,
+            value: value.into(),
+        });
+    }
+    Ok(value)
 }
 
-impl ScalarKalman {
-    fn new(A: Float, H: Float, Q: Float, R: Float, x0: Option<Float>, P0: Option<Float>) -> Self {
-        let x = if let Some(x0) = x0 { x0 } else { 0.0 };
-        let P = if let Some(P0) = P0 { P0 } else { 0.0 };
-        Self { x, P, A, H, Q, R }
+impl<T: FilterFloat> ScalarKalman<T> {
+    /// Create a filter with initial state `x0` and variance `P0` (both default to zero)
+    /// and control gain `B` (defaults to zero). Fails if `Q`, `R` or `P0` is negative.
+    pub fn new(
+        A: T,
+        H: T,
+        Q: T,
+        R: T,
+        x0: Option<T>,
+        P0: Option<T>,
+        B: Option<T>,
+    ) -> KalmanResult<Self> {
+        let x = x0.unwrap_or_else(T::zero);
+        let P = check_variance("P0", P0.unwrap_or_else(T::zero))?;
+        let B = B.unwrap_or_else(T::zero);
+        Ok(Self {
+            x,
+            P,
+            A,
+            H,
+            Q: check_variance("Q", Q)?,
+            R: check_variance("R", R)?,
+            B,
+            tol: T::of(DEFAULT_TOLERANCE),
+            form: UpdateForm::Standard,
+        })
+    }
+
+    /// Choose how `update` computes the posterior variance (defaults to
+    /// [`UpdateForm::Standard`]).
+    pub fn with_update_form(mut self, form: UpdateForm) -> Self {
+        self.form = form;
+        self
+    }
+
+    /// Form of the variance update.
+    pub fn update_form(&self) -> UpdateForm {
+        self.form
+    }
+
+    /// Set the tolerance used to detect a singular innovation variance or a negative
+    /// state variance in `update` (defaults to `1e-8`).
+    pub fn with_tolerance(mut self, tol: T) -> KalmanResult<Self> {
+        self.tol = check_variance("tol", tol)?;
+        Ok(self)
+    }
+
+    /// Tolerance used by `update`.
+    pub fn tol(&self) -> T {
+        self.tol
+    }
+
+    /// Current state estimate.
+    pub fn x(&self) -> T {
+        self.x
+    }
+
+    /// Current state variance.
+    pub fn P(&self) -> T {
+        self.P
+    }
+
+    /// State transition coefficient.
+    pub fn A(&self) -> T {
+        self.A
+    }
+
+    pub fn set_A(&mut self, A: T) {
+        self.A = A;
+    }
+
+    /// Measurement coefficient.
+    pub fn H(&self) -> T {
+        self.H
+    }
+
+    pub fn set_H(&mut self, H: T) {
+        self.H = H;
+    }
+
+    /// Process noise variance.
+    pub fn Q(&self) -> T {
+        self.Q
+    }
+
+    /// Set the process noise variance, failing if it is negative.
+    pub fn set_Q(&mut self, Q: T) -> KalmanResult<()> {
+        self.Q = check_variance("Q", Q)?;
+        Ok(())
+    }
+
+    /// Measurement noise variance.
+    pub fn R(&self) -> T {
+        self.R
+    }
+
+    /// Set the measurement noise variance, failing if it is negative.
+    pub fn set_R(&mut self, R: T) -> KalmanResult<()> {
+        self.R = check_variance("R", R)?;
+        Ok(())
+    }
+
+    /// Control gain.
+    pub fn B(&self) -> T {
+        self.B
+    }
+
+    pub fn set_B(&mut self, B: T) {
+        self.B = B;
+    }
+
+    /// Propagate the state one step without control input.
+    pub fn predict(&mut self) {
+        self.predict_controlled(T::zero());
     }
 
-    fn predict(&mut self) {
-        self.x *= self.A;
+    /// Propagate the state with control input `u` applied through the gain `B`.
+    pub fn predict_controlled(&mut self, u: T) {
+        self.x = self.A * self.x + self.B * u;
         self.P = self.A * self.P * self.A + self.Q;
     }
 
-    fn update(&mut self, z: Float) -> KalmanResult<()> {
+    /// Fail if the state variance is below `-tol`, and clamp it to zero if it is
+    /// negative within the tolerance.
+    fn check_nonnegative(&self, P: T) -> KalmanResult<T> {
+        if P < -self.tol {
+            return Err(KalmanError::NegativeVariance {
+                name: "P",
+                value: P.into(),
+            });
+        }
+        Ok(P.max(T::zero()))
+    }
+
+    /// Fuse measurement `z` into the state.
+    ///
+    /// The state is left unchanged if the update fails.
+    pub fn update(&mut self, z: T) -> KalmanResult<Innovation<T>> {
+        if !z.is_finite() {
+            return Err(KalmanError::NonFiniteInput {
+                name: "z",
+                value: z.into(),
+            });
+        }
+        check_finite("x", self.x)?;
+        self.check_nonnegative(check_finite("P", self.P)?)?;
+
         let y = z - self.H * self.x;
         let S = self.H * self.P * self.H + self.R;
-        if S.abs() < 1e-8 {
+        if S.abs() < self.tol {
             return Err(KalmanError::FailedScalarInverse {
                 scalar_name: "Innovation (measurement pre-fit residual `S`)",
             });
         }
-        let S_inv = 1. / S;
+        let S_inv = T::one() / S;
         let K = self.P * self.H * S_inv;
-        self.x += K * y;
-        self.P *= 1.0 - K * self.H;
-        Ok(())
+        let x = check_finite("x", self.x + K * y)?;
+        let I_KH = T::one() - K * self.H;
+        let P = match self.form {
+            UpdateForm::Standard => self.P * I_KH,
+            UpdateForm::Joseph => I_KH * self.P * I_KH + K * self.R * K,
+        };
+        let P = self.check_nonnegative(check_finite("P", P)?)?;
+        self.x = x;
+        self.P = P;
+        Ok(Innovation { y, S, K })
     }
 
-    fn advance(&mut self, z: Float) -> KalmanResult<Float> {
+    /// Predict and then update with `z`, returning the new state estimate.
+    pub fn advance(&mut self, z: T) -> KalmanResult<T> {
         self.predict();
         self.update(z)?;
         Ok(self.x)
     }
+
+    /// Filter `zs`, treating NaN or masked samples according to `policy`.
+    pub fn filter_series(
+        &mut self,
+        zs: &[T],
+        mask: Option<&[bool]>,
+        policy: MissingPolicy,
+    ) -> KalmanResult<Vec<T>> {
+        filter_series(self, zs, mask, policy)
+    }
+
+    /// Like `advance`, with control input `u`.
+    pub fn advance_controlled(&mut self, z: T, u: T) -> KalmanResult<T> {
+        self.predict_controlled(u);
+        self.update(z)?;
+        Ok(self.x)
+    }
+
+    /// Filter `zs` driven by the parallel control series `us`.
+    pub fn filter_controlled(&mut self, zs: &[T], us: &[T]) -> KalmanResult<Vec<T>> {
+        filter_controlled(self, zs, us)
+    }
+}
+
+/// The predict and update steps of a scalar filter, shared by [`ScalarKalman`] and
+/// [`SqrtKalman`] so the series analyses can run on either.
+pub trait ScalarFilter<T: FilterFloat> {
+    /// Model parameters and current state, with the state variance as `P`.
+    fn model(&self) -> &ScalarKalman<T>;
+    /// Propagate the state with control input `u` applied through the gain `B`.
+    fn predict_controlled(&mut self, u: T);
+    /// Fuse measurement `z` into the state, leaving it unchanged if the update fails.
+    fn update(&mut self, z: T) -> KalmanResult<Innovation<T>>;
+}
+
+impl<T: FilterFloat> ScalarFilter<T> for ScalarKalman<T> {
+    fn model(&self) -> &ScalarKalman<T> {
+        self
+    }
+
+    fn predict_controlled(&mut self, u: T) {
+        ScalarKalman::predict_controlled(self, u)
+    }
+
+    fn update(&mut self, z: T) -> KalmanResult<Innovation<T>> {
+        ScalarKalman::update(self, z)
+    }
 }
 
-#[pymethods]
-impl ScalarKalman {
-    #[new]
-    fn py_new(
-        A: Float,
-        H: Float,
-        Q: Float,
-        R: Float,
-        x0: Option<Float>,
-        P0: Option<Float>,
-    ) -> Self {
-        Self::new(A, H, Q, R, x0, P0)
+/// Step `filter` over a sample that may be missing, handling it according to `policy`.
+/// Returns the innovation of the update, or `None` if no update took place.
+pub(crate) fn step_or_missing<T: FilterFloat>(
+    filter: &mut impl ScalarFilter<T>,
+    z: T,
+    missing: bool,
+    policy: MissingPolicy,
+    index: usize,
+) -> KalmanResult<Option<Innovation<T>>> {
+    if !missing && !z.is_nan() {
+        filter.predict_controlled(T::zero());
+        return filter.update(z).map(Some).map_err(|e| e.at_step(index));
     }
-    #[pyo3(name = "advance")]
-    fn py_advance(&mut self, z: Float) -> PyResult<Float> {
-        Ok(self.advance(z)?)
+    if policy.predicts(index)? {
+        filter.predict_controlled(T::zero());
     }
+    Ok(None)
+}
+
+/// Like [`step_or_missing`], returning the new state estimate.
+pub(crate) fn advance_or_missing<T: FilterFloat>(
+    filter: &mut impl ScalarFilter<T>,
+    z: T,
+    missing: bool,
+    policy: MissingPolicy,
+    index: usize,
+) -> KalmanResult<T> {
+    step_or_missing(filter, z, missing, policy, index)?;
+    Ok(filter.model().x)
+}
+
+/// Filter `zs` with any scalar filter; see [`ScalarKalman::filter_series`].
+pub(crate) fn filter_series<T: FilterFloat>(
+    filter: &mut impl ScalarFilter<T>,
+    zs: &[T],
+    mask: Option<&[bool]>,
+    policy: MissingPolicy,
+) -> KalmanResult<Vec<T>> {
+    check_mask(mask, zs.len())?;
+    zs.iter()
+        .enumerate()
+        .map(|(i, &z)| {
+            let masked = mask.is_some_and(|mask| mask[i]);
+            advance_or_missing(filter, z, masked, policy, i)
+        })
+        .collect()
+}
+
+/// Filter `zs` with any scalar filter; see [`ScalarKalman::filter_controlled`].
+pub(crate) fn filter_controlled<T: FilterFloat>(
+    filter: &mut impl ScalarFilter<T>,
+    zs: &[T],
+    us: &[T],
+) -> KalmanResult<Vec<T>> {
+    check_len("u", us.len(), zs.len())?;
+    zs.iter()
+        .zip(us)
+        .enumerate()
+        .map(|(k, (&z, &u))| {
+            filter.predict_controlled(u);
+            filter.update(z).map_err(|e| e.at_step(k))?;
+            Ok(filter.model().x)
+        })
+        .collect()
+}
+
+/// A Python filter class and the Rust filter it wraps.
+#[cfg(feature = "python")]
+pub(crate) trait PyFilter: pyo3::PyClass + Clone {
+    type Inner: Clone;
+
+    fn inner(&self) -> &Self::Inner;
+
+    fn inner_mut(&mut self) -> &mut Self::Inner;
+}
+
+/// Define the `#[pymethods]` of a scalar filter class: accessors for the parameters of
+/// the `ScalarKalman` in field `$model`, the filter steps and copying, followed by the
+/// class-specific `$extra` methods (constructor, `__repr__` and pickling).
+#[cfg(feature = "python")]
+macro_rules! scalar_pymethods {
+    ($py_ty:ty, $float:ty, $model:tt, { $($extra:tt)* }) => {
+        #[pymethods]
+        impl $py_ty {
+            $($extra)*
+            /// Current state estimate.
+            #[getter(x)]
+            fn get_x(&self) -> $float {
+                self.x()
+            }
+            /// Current state variance.
+            #[getter(P)]
+            fn get_P(&self) -> $float {
+                self.P()
+            }
+            #[getter(A)]
+            fn get_A(&self) -> $float {
+                self.$model.A
+            }
+            #[setter(A)]
+            fn set_A(&mut self, A: $float) {
+                self.$model.set_A(A);
+            }
+            #[getter(H)]
+            fn get_H(&self) -> $float {
+                self.$model.H
+            }
+            #[setter(H)]
+            fn set_H(&mut self, H: $float) {
+                self.$model.set_H(H);
+            }
+            #[getter(Q)]
+            fn get_Q(&self) -> $float {
+                self.$model.Q
+            }
+            #[setter(Q)]
+            fn set_Q(&mut self, Q: $float) -> PyResult<()> {
+                Ok(self.$model.set_Q(Q)?)
+            }
+            #[getter(R)]
+            fn get_R(&self) -> $float {
+                self.$model.R
+            }
+            #[setter(R)]
+            fn set_R(&mut self, R: $float) -> PyResult<()> {
+                Ok(self.$model.set_R(R)?)
+            }
+            #[getter(B)]
+            fn get_B(&self) -> $float {
+                self.$model.B
+            }
+            #[setter(B)]
+            fn set_B(&mut self, B: $float) {
+                self.$model.set_B(B);
+            }
+            /// Tolerance for singular innovation and negative state variances.
+            #[getter(tol)]
+            fn get_tol(&self) -> $float {
+                self.$model.tol
+            }
+            #[setter(tol)]
+            fn set_tol(&mut self, tol: $float) -> PyResult<()> {
+                self.$model.tol = $crate::check_variance("tol", tol)?;
+                Ok(())
+            }
+            /// Propagate the state one step without a measurement, with optional control `u`.
+            #[pyo3(name = "predict")]
+            fn py_predict(&mut self, u: Option<$float>) {
+                self.predict_controlled(u.unwrap_or(0.0));
+            }
+            /// Fuse measurement `z`, returning the innovation `y` and gain `K` as `(y, K)`.
+            #[pyo3(name = "update")]
+            fn py_update(&mut self, z: $float) -> PyResult<($float, $float)> {
+                let $crate::Innovation { y, K, .. } = self.update(z)?;
+                Ok((y, K))
+            }
+            #[pyo3(name = "advance")]
+            fn py_advance(&mut self, z: $float, u: Option<$float>) -> PyResult<$float> {
+                Ok(self.advance_controlled(z, u.unwrap_or(0.0))?)
+            }
+            /// Forecast `n` steps ahead, returning `(means, variances)`, or
+            /// `(means, variances, lower, upper)` if a prediction interval `level` is given.
+            #[pyo3(name = "forecast")]
+            fn py_forecast(
+                &self,
+                py: Python,
+                n: usize,
+                level: Option<$crate::Float>,
+            ) -> PyResult<PyObject> {
+                use numpy::PyArray1;
+                let forecast = self.forecast(n);
+                let interval = level.map(|level| forecast.interval(level)).transpose()?;
+                let x = PyArray1::from_vec(py, forecast.x);
+                let P = PyArray1::from_vec(py, forecast.P);
+                Ok(match interval {
+                    Some((lower, upper)) => (
+                        x,
+                        P,
+                        PyArray1::from_vec(py, lower),
+                        PyArray1::from_vec(py, upper),
+                    )
+                        .into_py(py),
+                    None => (x, P).into_py(py),
+                })
+            }
+            fn __copy__(&self) -> Self {
+                self.clone()
+            }
+            fn __deepcopy__(&self, _memo: &PyAny) -> Self {
+                self.clone()
+            }
+        }
+    };
+}
+#[cfg(feature = "python")]
+pub(crate) use scalar_pymethods;
+
+/// Define a Python class wrapping a `ScalarKalman` of the given float type.
+#[cfg(feature = "python")]
+macro_rules! scalar_pyclass {
+    ($py_ty:ident, $float:ty, $name:literal) => {
+        #[derive(Debug, Clone)]
+        #[pyclass(name = $name, module = "kalman_no_control")]
+        pub(crate) struct $py_ty(pub(crate) ScalarKalman<$float>);
+
+        impl std::ops::Deref for $py_ty {
+            type Target = ScalarKalman<$float>;
+
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl std::ops::DerefMut for $py_ty {
+            fn deref_mut(&mut self) -> &mut Self::Target {
+                &mut self.0
+            }
+        }
+
+        impl From<ScalarKalman<$float>> for $py_ty {
+            fn from(filter: ScalarKalman<$float>) -> Self {
+                Self(filter)
+            }
+        }
+
+        impl PyFilter for $py_ty {
+            type Inner = ScalarKalman<$float>;
+
+            fn inner(&self) -> &Self::Inner {
+                &self.0
+            }
+
+            fn inner_mut(&mut self) -> &mut Self::Inner {
+                &mut self.0
+            }
+        }
+
+        scalar_pymethods!($py_ty, $float, 0, {
+            #[new]
+            #[allow(clippy::too_many_arguments)]
+            fn py_new(
+                A: $float,
+                H: $float,
+                Q: $float,
+                R: $float,
+                x0: Option<$float>,
+                P0: Option<$float>,
+                B: Option<$float>,
+                tol: Option<$float>,
+                form: Option<&str>,
+            ) -> PyResult<Self> {
+                let filter = ScalarKalman::new(A, H, Q, R, x0, P0, B)?
+                    .with_update_form(form.unwrap_or("standard").parse()?);
+                Ok(Self(match tol {
+                    Some(tol) => filter.with_tolerance(tol)?,
+                    None => filter,
+                }))
+            }
+            fn __repr__(&self) -> String {
+                format!(
+                    concat!(
+                        $name,
+                        "(A={:?}, H={:?}, Q={:?}, R={:?}, x0={:?}, P0={:?}, B={:?}, tol={:?}, ",
+                        "form='{}')"
+                    ),
+                    self.A,
+                    self.H,
+                    self.Q,
+                    self.R,
+                    self.x,
+                    self.P,
+                    self.B,
+                    self.tol,
+                    self.form.as_str()
+                )
+            }
+            /// Form of the variance update, `"standard"` or `"joseph"`.
+            #[getter(form)]
+            fn get_form(&self) -> &'static str {
+                self.form.as_str()
+            }
+            fn __getstate__(&self) -> ScalarState<$float> {
+                (
+                    self.A,
+                    self.H,
+                    self.Q,
+                    self.R,
+                    self.x,
+                    self.P,
+                    self.B,
+                    self.tol,
+                    self.form.as_str().to_owned(),
+                )
+            }
+            fn __setstate__(&mut self, state: ScalarState<$float>) -> PyResult<()> {
+                let (A, H, Q, R, x, P, B, tol, form) = state;
+                self.0 = ScalarKalman::new(A, H, Q, R, Some(x), Some(P), Some(B))?
+                    .with_tolerance(tol)?
+                    .with_update_form(form.parse()?);
+                Ok(())
+            }
+            fn __reduce__(&self, py: Python) -> (PyObject, ScalarState<$float>) {
+                // The state tuple doubles as the constructor arguments.
+                (py.get_type::<Self>().into(), self.__getstate__())
+            }
+        });
+    };
+}
+
+#[cfg(feature = "python")]
+scalar_pyclass!(PyScalarKalman, f64, "ScalarKalman");
+#[cfg(feature = "python")]
+scalar_pyclass!(PyScalarKalman32, f32, "ScalarKalman32");
+
+/// Evaluate `$body` with `$f` bound to the `&mut` Rust filter inside `$filter` and `$W`
+/// to its Python class, trying each of the given classes in turn. Without a list of
+/// classes, `$filter` may be a `ScalarKalman`, `ScalarKalman32` or `SqrtKalman`.
+#[cfg(feature = "python")]
+macro_rules! with_scalar_filter {
+    ($filter:expr, [$($class:ty),+], |$f:ident: $W:ident| $body:expr) => {{
+        $(if let Ok(mut $f) = $filter.extract::<PyRefMut<$class>>() {
+            #[allow(dead_code)]
+            type $W = $class;
+            let $f = $crate::PyFilter::inner_mut(&mut *$f);
+            $body
+        } else)+ {
+            let names = [$(<$class as pyo3::type_object::PyTypeInfo>::NAME),+];
+            let (last, rest) = names.split_last().expect("at least one class");
+            let names = match rest {
+                [] => last.to_string(),
+                _ => format!("{} or {}", rest.join(", "), last),
+            };
+            Err(pyo3::exceptions::PyTypeError::new_err(format!(
+                "filter must be a {names}"
+            )))
+        }
+    }};
+    ($filter:expr, [$($class:ty),+], |$f:ident| $body:expr) => {
+        with_scalar_filter!($filter, [$($class),+], |$f: _Class| $body)
+    };
+    ($filter:expr, |$f:ident $(: $W:ident)?| $body:expr) => {
+        with_scalar_filter!(
+            $filter,
+            [$crate::PyScalarKalman, $crate::PyScalarKalman32, $crate::SqrtKalman],
+            |$f $(: $W)?| $body
+        )
+    };
+}
+#[cfg(feature = "python")]
+pub(crate) use with_scalar_filter;
+
+/// Copy a one-dimensional array of the given float type out of NumPy.
+#[cfg(feature = "python")]
+fn read_series<T: FilterFloat + numpy::Element>(v: &PyAny) -> PyResult<Vec<T>> {
+    // Requiring the filter's own dtype rules out silent casts between precisions.
+    let v: PyReadonlyArray1<T> = v.extract()?;
+    Ok(v.as_array().iter().copied().collect())
+}
+
+/// Run a filter's `filter_series` as `run` on a measurement array of matching float type.
+#[cfg(feature = "python")]
+fn filter_array<'py, T, F>(
+    py: Python<'py>,
+    v: &PyAny,
+    mask: Option<PyReadonlyArray1<bool>>,
+    run: F,
+) -> PyResult<&'py PyAny>
+where
+    T: FilterFloat + numpy::Element,
+    F: FnOnce(&[T], Option<&[bool]>) -> KalmanResult<Vec<T>> + Send,
+{
+    // Copy the inputs out of NumPy so the loop can run without the GIL.
+    let zs: Vec<T> = read_series(v)?;
+    let mask = read_mask(mask, zs.len())?;
+    let out = py.allow_threads(|| run(&zs, mask.as_deref()))?;
+    Ok(PyArray1::from_vec(py, out).as_ref())
 }
 
+/// Filter a measurement series.
+///
+/// `filter` is a `ScalarKalman` or `SqrtKalman` with a float64 `v`, or a
+/// `ScalarKalman32` with a float32 `v`; the output has the same dtype.
+///
+/// Samples that are NaN, or `True` in the optional `mask`, are treated as missing and
+/// handled according to `missing`: `"predict"` (the default) propagates the state
+/// without an update, `"skip"` leaves the filter untouched and `"raise"` fails.
+///
+/// With `fixed_gain=True` the steady-state gain (see `ksteady`) is used throughout and
+/// the variance is not propagated; the filter is left with the steady-state variance.
+#[cfg(feature = "python")]
 #[pyfunction]
 fn kfilter<'py>(
     py: Python<'py>,
-    filter: &mut ScalarKalman,
-    v: PyReadonlyArray1<Float>,
-) -> PyResult<&'py PyArray1<Float>> {
-    let len = v.len();
-    let mut out = Vec::with_capacity(len);
-    let v = v.as_array();
-    for &v in v.iter() {
-        out.push(filter.advance(v)?)
-    }
-    Ok(PyArray1::from_vec(py, out))
+    filter: &PyAny,
+    v: &PyAny,
+    mask: Option<PyReadonlyArray1<bool>>,
+    missing: Option<&str>,
+    fixed_gain: Option<bool>,
+) -> PyResult<&'py PyAny> {
+    let policy: MissingPolicy = missing.unwrap_or("predict").parse()?;
+    let fixed_gain = fixed_gain.unwrap_or(false);
+    with_scalar_filter!(filter, |filter| {
+        filter_array(py, v, mask, |zs, mask| {
+            if fixed_gain {
+                filter.filter_fixed_gain(zs, mask, policy)
+            } else {
+                filter.filter_series(zs, mask, policy)
+            }
+        })
+    })
+}
+
+/// Filter a measurement series `v` driven by the parallel control series `u`.
+#[cfg(feature = "python")]
+#[pyfunction]
+fn kfilter_control<'py>(
+    py: Python<'py>,
+    filter: &PyAny,
+    v: &PyAny,
+    u: &PyAny,
+) -> PyResult<&'py PyAny> {
+    with_scalar_filter!(filter, |filter| {
+        let (zs, us) = (read_series(v)?, read_series(u)?);
+        let out = filter.filter_controlled(&zs, &us)?;
+        Ok(PyArray1::from_vec(py, out).as_ref())
+    })
 }
 
 /// A Python module implemented in Rust.
+#[cfg(feature = "python")]
 #[pymodule]
-fn kalman_no_control(_py: Python, m: &PyModule) -> PyResult<()> {
-    m.add_class::<ScalarKalman>()?;
+fn kalman_no_control(py: Python, m: &PyModule) -> PyResult<()> {
+    exceptions::register(py, m)?;
+    m.add_class::<PyScalarKalman>()?;
+    m.add_class::<PyScalarKalman32>()?;
+    m.add_class::<SqrtKalman>()?;
+    m.add_class::<information::InformationKalman>()?;
     m.add_function(wrap_pyfunction!(kfilter, m)?)?;
+    m.add_function(wrap_pyfunction!(kfilter_control, m)?)?;
+    m.add_function(wrap_pyfunction!(batch::kfilter_batch, m)?)?;
+    m.add_function(wrap_pyfunction!(trajectory::kfilter_full, m)?)?;
+    m.add_function(wrap_pyfunction!(likelihood::kloglik, m)?)?;
+    m.add_function(wrap_pyfunction!(varying::kfilter_varying, m)?)?;
+    m.add_class::<continuous::ContinuousKalman>()?;
+    m.add_function(wrap_pyfunction!(continuous::kfilter_timed, m)?)?;
+    m.add_function(wrap_pyfunction!(fit::kfit, m)?)?;
+    m.add_function(wrap_pyfunction!(smoother::ksmooth, m)?)?;
+    m.add_function(wrap_pyfunction!(steady::ksteady, m)?)?;
+    m.add_class::<multivariate::Kalman>()?;
+    m.add_function(wrap_pyfunction!(multivariate::kfilter_nd, m)?)?;
+    m.add_class::<ekf::ExtendedKalman>()?;
+    m.add_function(wrap_pyfunction!(ekf::kfilter_ekf, m)?)?;
+    m.add_class::<ukf::UnscentedKalman>()?;
+    m.add_function(wrap_pyfunction!(ukf::kfilter_ukf, m)?)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn setters_validate_noise_variances() {
+        let mut filter = ScalarKalman::new(0.9, 1.0, 0.1, 0.5, None, None, None).unwrap();
+        filter.set_A(0.5);
+        filter.set_Q(0.2).unwrap();
+        assert_eq!((filter.A(), filter.Q()), (0.5, 0.2));
+        assert!(matches!(
+            filter.set_R(-1.0),
+            Err(KalmanError::InvalidParameter { name: "R", .. })
+        ));
+        assert_eq!(filter.R(), 0.5);
+    }
+
+    /// A diffuse prior with a precise measurement: the standard update rounds the
+    /// posterior variance to about `-6.7e-7`, while the true value is `R / H^2`.
+    fn diffuse_filter() -> ScalarKalman {
+        ScalarKalman::new(1.0, 0.7, 0.0, 1e-8, None, Some(3e9), None).unwrap()
+    }
+
+    #[test]
+    fn standard_update_rejects_negative_variance() {
+        let mut filter = diffuse_filter();
+        assert!(matches!(
+            filter.update(1.0),
+            Err(KalmanError::NegativeVariance { name: "P", .. })
+        ));
+        assert_eq!(
+            filter.P(),
+            3e9,
+            "failed update must leave the state unchanged"
+        );
+    }
+
+    #[test]
+    fn joseph_update_stays_nonnegative() {
+        let mut filter = diffuse_filter().with_update_form(UpdateForm::Joseph);
+        filter.update(1.0).unwrap();
+        let exact = 1e-8 / (0.7 * 0.7);
+        assert!(
+            (filter.P() - exact).abs() < 1e-3 * exact,
+            "P = {}",
+            filter.P()
+        );
+    }
+
+    #[test]
+    fn clamps_negative_variance_within_tolerance() {
+        let mut filter = diffuse_filter().with_tolerance(1e-6).unwrap();
+        filter.update(1.0).unwrap();
+        assert_eq!(filter.P(), 0.0);
+    }
+
+    /// Unwrap the step and underlying error of a failed series.
+    fn failed_step<T: std::fmt::Debug>(result: KalmanResult<T>) -> (usize, KalmanError) {
+        match result.unwrap_err() {
+            KalmanError::AtStep { index, source } => (index, *source),
+            error => panic!("error without a step: {error:?}"),
+        }
+    }
+
+    #[test]
+    fn series_report_non_finite_input_step() {
+        let mut filter = ScalarKalman::new(0.9, 1.0, 0.1, 0.5, None, None, None).unwrap();
+        let zs = [1.0, 2.0, Float::INFINITY];
+        let (index, source) = failed_step(filter.clone().filter_series(
+            &zs,
+            None,
+            MissingPolicy::Raise,
+        ));
+        assert_eq!(index, 2);
+        assert!(matches!(
+            source,
+            KalmanError::NonFiniteInput { name: "z", .. }
+        ));
+        let (index, source) = failed_step(filter.filter_controlled(&zs, &[0.0; 3]));
+        assert_eq!(index, 2);
+        assert!(matches!(
+            source,
+            KalmanError::NonFiniteInput { name: "z", .. }
+        ));
+    }
+
+    #[test]
+    fn series_report_divergence_step() {
+        let mut filter = ScalarKalman::new(1e200, 1.0, 0.0, 0.5, Some(1.0), None, None).unwrap();
+        let (index, source) =
+            failed_step(filter.filter_series(&[1.0, 1.0], None, MissingPolicy::Raise));
+        assert_eq!(index, 1);
+        assert!(matches!(source, KalmanError::Diverged { name: "x", .. }));
+    }
+
+    #[test]
+    fn series_report_negative_variance_step() {
+        let mut filter = diffuse_filter();
+        let mask = [true, false];
+        let result = filter.filter_series(&[1.0, 1.0], Some(&mask), MissingPolicy::Skip);
+        let (index, source) = failed_step(result);
+        assert_eq!(index, 1);
+        assert!(matches!(
+            source,
+            KalmanError::NegativeVariance { name: "P", .. }
+        ));
+    }
+
+    #[test]
+    fn series_report_singular_innovation_below_tolerance() {
+        let filter = ScalarKalman::new(0.9, 1.0, 0.0, 0.5, None, None, None).unwrap();
+        // `S = R = 0.5` is fine under the default tolerance but singular under 1.
+        filter
+            .clone()
+            .filter_series(&[1.0, 1.0], None, MissingPolicy::Raise)
+            .unwrap();
+        let mut strict = filter.with_tolerance(1.0).unwrap();
+        let (index, source) =
+            failed_step(strict.filter_series(&[1.0, 1.0], None, MissingPolicy::Raise));
+        assert_eq!(index, 0);
+        assert!(matches!(source, KalmanError::FailedScalarInverse { .. }));
+    }
+
+    #[test]
+    fn missing_samples_follow_policy() {
+        let filter = ScalarKalman::new(0.9, 1.0, 0.1, 0.5, Some(1.0), Some(1.0), None).unwrap();
+        let mut observed = filter.clone();
+        observed.advance(1.0).unwrap();
+        let (x, P) = (observed.x(), observed.P());
+        // A NaN sample and a masked-out finite sample are both missing.
+        let inputs: [(&[Float], Option<&[bool]>); 2] = [
+            (&[1.0, Float::NAN], None),
+            (&[1.0, 5.0], Some(&[false, true])),
+        ];
+        for (zs, mask) in inputs {
+            let mut skip = filter.clone();
+            let out = skip.filter_series(zs, mask, MissingPolicy::Skip).unwrap();
+            assert_eq!(out, [x, x]);
+            assert_eq!(skip.P(), P);
+
+            let mut predict = filter.clone();
+            let out = predict
+                .filter_series(zs, mask, MissingPolicy::Predict)
+                .unwrap();
+            assert_eq!(out, [x, 0.9 * x]);
+            assert_eq!(predict.P(), 0.9 * P * 0.9 + 0.1);
+
+            let result = filter.clone().filter_series(zs, mask, MissingPolicy::Raise);
+            assert!(matches!(
+                result,
+                Err(KalmanError::MissingMeasurement { index: 1 })
+            ));
+        }
+    }
+
+    #[test]
+    fn mask_must_match_series_length() {
+        let mut filter = ScalarKalman::new(0.9, 1.0, 0.1, 0.5, None, None, None).unwrap();
+        let result = filter.filter_series(&[1.0, 2.0], Some(&[false]), MissingPolicy::Skip);
+        assert!(matches!(
+            result,
+            Err(KalmanError::DimensionMismatch { name: "mask", .. })
+        ));
+    }
+}
@@ -0,0 +1,75 @@
+#[cfg(feature = "python")]
+use numpy::PyReadonlyArray1;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+use crate::{
+    check_mask, step_or_missing, FilterFloat, Float, KalmanResult, MissingPolicy, ScalarFilter,
+    ScalarKalman, SqrtKalman,
+};
+#[cfg(feature = "python")]
+use crate::{read_mask, read_series, with_scalar_filter};
+
+impl<T: FilterFloat> ScalarKalman<T> {
+    /// Filter `zs` and return the total Gaussian log-likelihood of the innovations.
+    ///
+    /// Missing samples contribute nothing to the total.
+    pub fn log_likelihood(
+        &mut self,
+        zs: &[T],
+        mask: Option<&[bool]>,
+        policy: MissingPolicy,
+    ) -> KalmanResult<T> {
+        log_likelihood(self, zs, mask, policy)
+    }
+}
+
+impl SqrtKalman {
+    /// Filter `zs` and return the total log-likelihood, as
+    /// [`ScalarKalman::log_likelihood`].
+    pub fn log_likelihood(
+        &mut self,
+        zs: &[Float],
+        mask: Option<&[bool]>,
+        policy: MissingPolicy,
+    ) -> KalmanResult<Float> {
+        log_likelihood(self, zs, mask, policy)
+    }
+}
+
+fn log_likelihood<T: FilterFloat>(
+    filter: &mut impl ScalarFilter<T>,
+    zs: &[T],
+    mask: Option<&[bool]>,
+    policy: MissingPolicy,
+) -> KalmanResult<T> {
+    check_mask(mask, zs.len())?;
+    let mut total = T::zero();
+    for (i, &z) in zs.iter().enumerate() {
+        let masked = mask.is_some_and(|mask| mask[i]);
+        if let Some(inn) = step_or_missing(filter, z, masked, policy, i)? {
+            total = total + inn.log_likelihood();
+        }
+    }
+    Ok(total)
+}
+
+/// Filter a measurement series like `kfilter`, returning the total log-likelihood.
+#[cfg(feature = "python")]
+#[pyfunction]
+pub(crate) fn kloglik(
+    py: Python,
+    filter: &PyAny,
+    v: &PyAny,
+    mask: Option<PyReadonlyArray1<bool>>,
+    missing: Option<&str>,
+) -> PyResult<PyObject> {
+    let policy: MissingPolicy = missing.unwrap_or("predict").parse()?;
+    with_scalar_filter!(filter, |filter| {
+        let zs = read_series(v)?;
+        let mask = read_mask(mask, zs.len())?;
+        Ok(filter
+            .log_likelihood(&zs, mask.as_deref(), policy)?
+            .into_py(py))
+    })
+}
@@ -0,0 +1,123 @@
+use ndarray::Array2;
+
+use crate::Float;
+
+/// Pivots smaller than this, relative to the largest entry of the matrix, are treated
+/// as zero, so the test does not depend on the scale of the matrix.
+const PIVOT_TOLERANCE: Float = 1e-12;
+
+/// Largest absolute entry of `m`.
+fn scale(m: &Array2<Float>) -> Float {
+    m.iter().fold(0.0, |max, v| max.max(v.abs()))
+}
+
+/// Invert a square matrix using Gauss-Jordan elimination with partial pivoting.
+///
+/// Returns `None` if the matrix is not square or is (numerically) singular.
+pub(crate) fn invert(m: &Array2<Float>) -> Option<Array2<Float>> {
+    let n = m.nrows();
+    if m.ncols() != n {
+        return None;
+    }
+    let tol = PIVOT_TOLERANCE * scale(m);
+    let mut a = m.clone();
+    let mut inv = Array2::eye(n);
+    for col in 0..n {
+        let pivot = (col..n).max_by(|&i, &j| a[[i, col]].abs().total_cmp(&a[[j, col]].abs()))?;
+        if a[[pivot, col]].abs() <= tol {
+            return None;
+        }
+        if pivot != col {
+            for k in 0..n {
+                a.swap([pivot, k], [col, k]);
+                inv.swap([pivot, k], [col, k]);
+            }
+        }
+        let d = a[[col, col]];
+        for k in 0..n {
+            a[[col, k]] /= d;
+            inv[[col, k]] /= d;
+        }
+        for row in 0..n {
+            if row == col {
+                continue;
+            }
+            let f = a[[row, col]];
+            if f == 0.0 {
+                continue;
+            }
+            for k in 0..n {
+                a[[row, k]] -= f * a[[col, k]];
+                inv[[row, k]] -= f * inv[[col, k]];
+            }
+        }
+    }
+    Some(inv)
+}
+
+/// Lower-triangular Cholesky factor `L` of a symmetric positive-semidefinite matrix, so
+/// that `L * L^T == m`.
+///
+/// Returns `None` if the matrix is not square or has a (numerically) negative pivot.
+pub(crate) fn cholesky(m: &Array2<Float>) -> Option<Array2<Float>> {
+    let n = m.nrows();
+    if m.ncols() != n {
+        return None;
+    }
+    let tol = PIVOT_TOLERANCE * scale(m);
+    let mut L = Array2::<Float>::zeros((n, n));
+    for i in 0..n {
+        for j in 0..=i {
+            let mut sum = m[[i, j]];
+            for k in 0..j {
+                sum -= L[[i, k]] * L[[j, k]];
+            }
+            if i == j {
+                if sum < -tol {
+                    return None;
+                }
+                L[[i, i]] = sum.max(0.0).sqrt();
+            } else if L[[j, j]] > 0.0 {
+                L[[i, j]] = sum / L[[j, j]];
+            }
+        }
+    }
+    Some(L)
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::array;
+
+    use super::*;
+
+    fn assert_close(a: &Array2<Float>, b: &Array2<Float>, tol: Float) {
+        let error = scale(&(a - b));
+        assert!(error <= tol * scale(b), "{a} vs {b}");
+    }
+
+    #[test]
+    fn inverse_times_matrix_is_identity() {
+        let m = array![[4.0, 1.0, 0.5], [1.0, 3.0, 0.2], [0.5, 0.2, 2.0]];
+        let inv = invert(&m).unwrap();
+        assert_close(&inv.dot(&m), &Array2::eye(3), 1e-14);
+        // Only the conditioning matters, not the scale.
+        let small = Array2::eye(2) * 1e-9;
+        assert_close(&invert(&small).unwrap(), &(Array2::eye(2) * 1e9), 1e-15);
+    }
+
+    #[test]
+    fn singular_matrix_has_no_inverse() {
+        assert!(invert(&array![[1.0, 2.0], [2.0, 4.0]]).is_none());
+        assert!(invert(&Array2::zeros((2, 2))).is_none());
+        assert!(invert(&Array2::zeros((2, 3))).is_none());
+    }
+
+    #[test]
+    fn cholesky_factor_reproduces_matrix() {
+        let m = array![[4.0, 2.0, 0.4], [2.0, 5.0, 1.0], [0.4, 1.0, 3.0]];
+        let L = cholesky(&m).unwrap();
+        assert_close(&L.dot(&L.t()), &m, 1e-15);
+        assert!(cholesky(&array![[1.0, 2.0], [2.0, 1.0]]).is_none());
+    }
+}
@@ -0,0 +1,213 @@
+use ndarray::{Array1, Array2, ArrayView1};
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray1, PyArray2, PyReadonlyArray1, PyReadonlyArray2};
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+use crate::linalg::invert;
+use crate::{Float, KalmanError, KalmanResult};
+
+/// Kalman filter for the linear model `x' = A x + w`, `z = H x + v`, where the process
+/// noise `w` and measurement noise `v` have covariances `Q` and `R`.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "python", pyclass)]
+pub struct Kalman {
+    x: Array1<Float>,
+    P: Array2<Float>,
+    A: Array2<Float>,
+    H: Array2<Float>,
+    Q: Array2<Float>,
+    R: Array2<Float>,
+}
+
+pub(crate) fn check_shape(
+    name: &'static str,
+    m: &Array2<Float>,
+    rows: usize,
+    cols: usize,
+) -> KalmanResult<()> {
+    if m.dim() != (rows, cols) {
+        return Err(KalmanError::DimensionMismatch {
+            name,
+            expected: vec![rows, cols],
+            found: m.shape().to_vec(),
+        });
+    }
+    Ok(())
+}
+
+/// Check that a vector has `len` entries.
+pub(crate) fn check_vec_len(
+    name: &'static str,
+    v: &ArrayView1<Float>,
+    len: usize,
+) -> KalmanResult<()> {
+    if v.len() != len {
+        return Err(KalmanError::DimensionMismatch {
+            name,
+            expected: vec![len],
+            found: vec![v.len()],
+        });
+    }
+    Ok(())
+}
+
+impl Kalman {
+    /// Create a filter with initial state `x0` and covariance `P0` (both default to
+    /// zero). Fails if the matrix shapes are inconsistent.
+    pub fn new(
+        A: Array2<Float>,
+        H: Array2<Float>,
+        Q: Array2<Float>,
+        R: Array2<Float>,
+        x0: Option<Array1<Float>>,
+        P0: Option<Array2<Float>>,
+    ) -> KalmanResult<Self> {
+        let n = A.nrows();
+        let m = H.nrows();
+        check_shape("A", &A, n, n)?;
+        check_shape("H", &H, m, n)?;
+        check_shape("Q", &Q, n, n)?;
+        check_shape("R", &R, m, m)?;
+        let x = x0.unwrap_or_else(|| Array1::zeros(n));
+        check_vec_len("x0", &x.view(), n)?;
+        let P = P0.unwrap_or_else(|| Array2::zeros((n, n)));
+        check_shape("P0", &P, n, n)?;
+        Ok(Self { x, P, A, H, Q, R })
+    }
+
+    /// Current state estimate.
+    pub fn x(&self) -> &Array1<Float> {
+        &self.x
+    }
+
+    /// Current state covariance.
+    pub fn P(&self) -> &Array2<Float> {
+        &self.P
+    }
+
+    pub fn predict(&mut self) {
+        self.x = self.A.dot(&self.x);
+        self.P = self.A.dot(&self.P).dot(&self.A.t()) + &self.Q;
+    }
+
+    pub fn update(&mut self, z: ArrayView1<Float>) -> KalmanResult<()> {
+        check_vec_len("z", &z, self.H.nrows())?;
+        let y = &z - &self.H.dot(&self.x);
+        let PHt = self.P.dot(&self.H.t());
+        let S = self.H.dot(&PHt) + &self.R;
+        let S_inv = invert(&S).ok_or(KalmanError::FailedMatrixInverse {
+            matrix_name: "Innovation (measurement pre-fit residual `S`)",
+        })?;
+        let K = PHt.dot(&S_inv);
+        self.x += &K.dot(&y);
+        let I_KH = Array2::<Float>::eye(self.x.len()) - K.dot(&self.H);
+        self.P = I_KH.dot(&self.P);
+        Ok(())
+    }
+
+    pub fn advance(&mut self, z: ArrayView1<Float>) -> KalmanResult<&Array1<Float>> {
+        self.predict();
+        self.update(z)?;
+        Ok(&self.x)
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl Kalman {
+    #[new]
+    fn py_new(
+        A: PyReadonlyArray2<Float>,
+        H: PyReadonlyArray2<Float>,
+        Q: PyReadonlyArray2<Float>,
+        R: PyReadonlyArray2<Float>,
+        x0: Option<PyReadonlyArray1<Float>>,
+        P0: Option<PyReadonlyArray2<Float>>,
+    ) -> PyResult<Self> {
+        Ok(Self::new(
+            A.to_owned_array(),
+            H.to_owned_array(),
+            Q.to_owned_array(),
+            R.to_owned_array(),
+            x0.map(|x0| x0.to_owned_array()),
+            P0.map(|P0| P0.to_owned_array()),
+        )?)
+    }
+    /// Current state estimate.
+    #[getter(x)]
+    fn get_x<'py>(&self, py: Python<'py>) -> &'py PyArray1<Float> {
+        self.x.clone().into_pyarray(py)
+    }
+    /// Current state covariance.
+    #[getter(P)]
+    fn get_P<'py>(&self, py: Python<'py>) -> &'py PyArray2<Float> {
+        self.P.clone().into_pyarray(py)
+    }
+    /// Propagate the state one step without a measurement.
+    #[pyo3(name = "predict")]
+    fn py_predict(&mut self) {
+        self.predict();
+    }
+    /// Fuse measurement vector `z`.
+    #[pyo3(name = "update")]
+    fn py_update(&mut self, z: PyReadonlyArray1<Float>) -> PyResult<()> {
+        Ok(self.update(z.as_array())?)
+    }
+    #[pyo3(name = "advance")]
+    fn py_advance<'py>(
+        &mut self,
+        py: Python<'py>,
+        z: PyReadonlyArray1<Float>,
+    ) -> PyResult<&'py PyArray1<Float>> {
+        Ok(self.advance(z.as_array())?.clone().into_pyarray(py))
+    }
+}
+
+/// Filter a series of measurement vectors, one per row of `v`, returning one
+/// state estimate per row.
+#[cfg(feature = "python")]
+#[pyfunction]
+pub(crate) fn kfilter_nd<'py>(
+    py: Python<'py>,
+    filter: &mut Kalman,
+    v: PyReadonlyArray2<Float>,
+) -> PyResult<&'py PyArray2<Float>> {
+    let v = v.as_array();
+    let mut out = Array2::zeros((v.nrows(), filter.x.len()));
+    for (z, mut row) in v.outer_iter().zip(out.outer_iter_mut()) {
+        row.assign(filter.advance(z)?);
+    }
+    Ok(out.into_pyarray(py))
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::array;
+
+    use super::*;
+    use crate::ScalarKalman;
+
+    #[test]
+    fn one_dimensional_filter_matches_scalar_filter() {
+        let (A, H, Q, R) = (0.95, 2.0, 0.1, 0.5);
+        let mut scalar = ScalarKalman::new(A, H, Q, R, Some(0.3), Some(2.0), None).unwrap();
+        let mut filter = Kalman::new(
+            array![[A]],
+            array![[H]],
+            array![[Q]],
+            array![[R]],
+            Some(array![0.3]),
+            Some(array![[2.0]]),
+        )
+        .unwrap();
+        for k in 0..50 {
+            let z = (0.3 * k as Float).sin();
+            scalar.advance(z).unwrap();
+            filter.advance(array![z].view()).unwrap();
+            let (x, P) = (filter.x()[0], filter.P()[[0, 0]]);
+            assert!((x - scalar.x()).abs() <= 1e-14 * x.abs(), "x at step {k}");
+            assert!((P - scalar.P()).abs() <= 1e-14 * P, "P at step {k}");
+        }
+    }
+}
@@ -0,0 +1,124 @@
+#[cfg(feature = "python")]
+use numpy::PyArray1;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+#[cfg(feature = "python")]
+use crate::{read_series, with_scalar_filter};
+use crate::{
+    FilterFloat, Float, KalmanError, KalmanResult, ScalarFilter, ScalarKalman, SqrtKalman,
+};
+
+/// Smoothed state means and variances, one entry per measurement.
+#[derive(Debug, Clone)]
+pub struct Smoothed<T = Float> {
+    pub x: Vec<T>,
+    pub P: Vec<T>,
+    /// Smoothed lag-one covariances `Cov(x[k], x[k - 1])`; the first entry is zero.
+    pub P_lag: Vec<T>,
+}
+
+impl<T: FilterFloat> ScalarKalman<T> {
+    /// Run a Rauch–Tung–Striebel smoother over `zs`.
+    ///
+    /// The forward pass advances the filter exactly like `kfilter`, so on return the
+    /// filter holds the final filtered (not smoothed) state.
+    pub fn smooth(&mut self, zs: &[T]) -> KalmanResult<Smoothed<T>> {
+        smooth(self, zs)
+    }
+}
+
+impl SqrtKalman {
+    /// Run a Rauch–Tung–Striebel smoother over `zs`, as [`ScalarKalman::smooth`], with
+    /// the forward pass in square-root form.
+    pub fn smooth(&mut self, zs: &[Float]) -> KalmanResult<Smoothed> {
+        smooth(self, zs)
+    }
+}
+
+fn smooth<T: FilterFloat>(
+    filter: &mut impl ScalarFilter<T>,
+    zs: &[T],
+) -> KalmanResult<Smoothed<T>> {
+    let len = zs.len();
+    let mut x_prior = Vec::with_capacity(len);
+    let mut P_prior = Vec::with_capacity(len);
+    let mut x_post = Vec::with_capacity(len);
+    let mut P_post = Vec::with_capacity(len);
+    for (k, &z) in zs.iter().enumerate() {
+        filter.predict_controlled(T::zero());
+        x_prior.push(filter.model().x);
+        P_prior.push(filter.model().P);
+        filter.update(z).map_err(|e| e.at_step(k))?;
+        x_post.push(filter.model().x);
+        P_post.push(filter.model().P);
+    }
+
+    let (A, tol) = (filter.model().A, filter.model().tol);
+    let mut x = x_post;
+    let mut P = P_post;
+    let mut P_lag = vec![T::zero(); len];
+    for k in (0..len.saturating_sub(1)).rev() {
+        if P_prior[k + 1].abs() < tol {
+            return Err(KalmanError::FailedScalarInverse {
+                scalar_name: "Predicted variance (`P` prior)",
+            });
+        }
+        let C = P[k] * A / P_prior[k + 1];
+        x[k] = x[k] + C * (x[k + 1] - x_prior[k + 1]);
+        P[k] = P[k] + C * C * (P[k + 1] - P_prior[k + 1]);
+        P_lag[k + 1] = C * P[k + 1];
+    }
+    Ok(Smoothed { x, P, P_lag })
+}
+
+/// Smooth a measurement series, returning `(states, variances)`.
+#[cfg(feature = "python")]
+#[pyfunction]
+pub(crate) fn ksmooth(py: Python, filter: &PyAny, v: &PyAny) -> PyResult<PyObject> {
+    with_scalar_filter!(filter, |filter| {
+        let Smoothed { x, P, .. } = filter.smooth(&read_series(v)?)?;
+        Ok((PyArray1::from_vec(py, x), PyArray1::from_vec(py, P)).into_py(py))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MissingPolicy;
+
+    fn series() -> Vec<Float> {
+        (0..40).map(|k| (0.3 * k as Float).sin()).collect()
+    }
+
+    #[test]
+    fn ends_at_filtered_state_with_smaller_variances() {
+        let filter = ScalarKalman::new(0.9, 1.0, 0.1, 0.5, None, Some(1.0), None).unwrap();
+        let zs = series();
+        let trajectory = filter
+            .clone()
+            .trajectory(&zs, None, MissingPolicy::Raise)
+            .unwrap();
+        let Smoothed { x, P, .. } = filter.clone().smooth(&zs).unwrap();
+        assert_eq!(x.last(), trajectory.x.last());
+        assert_eq!(P.last(), trajectory.P.last());
+        for (k, (P, P_filtered)) in P.iter().zip(&trajectory.P).enumerate() {
+            assert!(P <= P_filtered, "step {k}: {P} > {P_filtered}");
+        }
+    }
+
+    #[test]
+    fn matches_hand_computed_values() {
+        // Filtered: x = [0.5, 1.4], P = [0.5, 0.6]; predicted P[1] = 1.5, so the
+        // smoother gain for step 0 is 0.5 / 1.5.
+        let mut filter = ScalarKalman::new(1.0, 1.0, 1.0, 1.0, None, None, None).unwrap();
+        let Smoothed { x, P, P_lag } = filter.smooth(&[1.0, 2.0]).unwrap();
+        let expected: [(Vec<Float>, [Float; 2]); 3] =
+            [(x, [0.8, 1.4]), (P, [0.4, 0.6]), (P_lag, [0.0, 0.2])];
+        for (actual, expected) in expected {
+            for (a, e) in actual.iter().zip(expected) {
+                assert!((a - e).abs() < 1e-15, "{actual:?} vs {expected:?}");
+            }
+        }
+    }
+}
@@ -0,0 +1,291 @@
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+use crate::{
+    check_finite, filter_series, Float, Innovation, KalmanError, KalmanResult, MissingPolicy,
+    ScalarFilter, ScalarKalman,
+};
+#[cfg(feature = "python")]
+use crate::{check_variance, scalar_pymethods, PyFilter};
+
+/// Square-root form of [`ScalarKalman`], propagating the standard deviation
+/// `sqrt(P)` instead of the variance.
+///
+/// Squaring happens only when a variance is reported, so the variance can never turn
+/// negative and tiny variances keep twice the relative precision. In the scalar case
+/// the square-root and UD factorizations coincide.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "python", pyclass(module = "kalman_no_control"))]
+pub struct SqrtKalman {
+    /// Model parameters and state; its `P` is kept equal to `sqrt_P^2`.
+    model: ScalarKalman,
+    sqrt_P: Float,
+}
+
+impl SqrtKalman {
+    /// Create a filter with the same parameters and defaults as [`ScalarKalman::new`].
+    pub fn new(
+        A: Float,
+        H: Float,
+        Q: Float,
+        R: Float,
+        x0: Option<Float>,
+        P0: Option<Float>,
+        B: Option<Float>,
+    ) -> KalmanResult<Self> {
+        Ok(ScalarKalman::new(A, H, Q, R, x0, P0, B)?.into())
+    }
+
+    /// Set the tolerance below which the innovation variance is treated as singular.
+    pub fn with_tolerance(mut self, tol: Float) -> KalmanResult<Self> {
+        self.model = self.model.with_tolerance(tol)?;
+        Ok(self)
+    }
+
+    /// Current state estimate.
+    pub fn x(&self) -> Float {
+        self.model.x
+    }
+
+    /// Current state variance.
+    pub fn P(&self) -> Float {
+        self.model.P
+    }
+
+    /// Square root of the current state variance.
+    pub fn sqrt_P(&self) -> Float {
+        self.sqrt_P
+    }
+
+    fn set_sqrt_P(&mut self, sqrt_P: Float) {
+        self.sqrt_P = sqrt_P;
+        self.model.P = sqrt_P * sqrt_P;
+    }
+
+    /// Propagate the state one step without control input.
+    pub fn predict(&mut self) {
+        self.predict_controlled(0.0);
+    }
+
+    /// Propagate the state with control input `u` applied through the gain `B`.
+    pub fn predict_controlled(&mut self, u: Float) {
+        let m = &mut self.model;
+        m.x = m.A * m.x + m.B * u;
+        let sqrt_P = (m.A * self.sqrt_P).hypot(m.Q.sqrt());
+        self.set_sqrt_P(sqrt_P);
+    }
+
+    /// Fuse measurement `z` into the state.
+    ///
+    /// The state is left unchanged if the update fails.
+    pub fn update(&mut self, z: Float) -> KalmanResult<Innovation> {
+        if !z.is_finite() {
+            return Err(KalmanError::NonFiniteInput {
+                name: "z",
+                value: z,
+            });
+        }
+        let m = &self.model;
+        check_finite("x", m.x)?;
+        check_finite("P", self.sqrt_P)?;
+
+        let y = z - m.H * m.x;
+        let HP = m.H * self.sqrt_P;
+        let S = HP * HP + m.R;
+        if S < m.tol {
+            return Err(KalmanError::FailedScalarInverse {
+                scalar_name: "Innovation (measurement pre-fit residual `S`)",
+            });
+        }
+        let K = self.sqrt_P * HP / S;
+        let x = check_finite("x", m.x + K * y)?;
+        // sqrt(P (1 - K H)) = sqrt(P) sqrt(R / S), which is non-negative by construction.
+        let sqrt_P = self.sqrt_P * (m.R / S).sqrt();
+        self.model.x = x;
+        self.set_sqrt_P(sqrt_P);
+        Ok(Innovation { y, S, K })
+    }
+
+    /// Predict and then update with `z`, returning the new state estimate.
+    pub fn advance(&mut self, z: Float) -> KalmanResult<Float> {
+        self.advance_controlled(z, 0.0)
+    }
+
+    /// Like `advance`, with control input `u`.
+    pub fn advance_controlled(&mut self, z: Float, u: Float) -> KalmanResult<Float> {
+        self.predict_controlled(u);
+        self.update(z)?;
+        Ok(self.x())
+    }
+
+    /// Filter `zs`, treating NaN or masked samples according to `policy`.
+    pub fn filter_series(
+        &mut self,
+        zs: &[Float],
+        mask: Option<&[bool]>,
+        policy: MissingPolicy,
+    ) -> KalmanResult<Vec<Float>> {
+        filter_series(self, zs, mask, policy)
+    }
+
+    /// Filter `zs` driven by the parallel control series `us`.
+    pub fn filter_controlled(&mut self, zs: &[Float], us: &[Float]) -> KalmanResult<Vec<Float>> {
+        crate::filter_controlled(self, zs, us)
+    }
+
+    /// Filter `zs` with the constant steady-state gain, as
+    /// [`ScalarKalman::filter_fixed_gain`].
+    pub fn filter_fixed_gain(
+        &mut self,
+        zs: &[Float],
+        mask: Option<&[bool]>,
+        policy: MissingPolicy,
+    ) -> KalmanResult<Vec<Float>> {
+        let out = self.model.filter_fixed_gain(zs, mask, policy);
+        self.sqrt_P = self.model.P.sqrt();
+        out
+    }
+}
+
+impl From<ScalarKalman> for SqrtKalman {
+    fn from(model: ScalarKalman) -> Self {
+        let sqrt_P = model.P.sqrt();
+        let mut filter = Self { model, sqrt_P };
+        filter.set_sqrt_P(sqrt_P);
+        filter
+    }
+}
+
+impl From<SqrtKalman> for ScalarKalman {
+    fn from(filter: SqrtKalman) -> Self {
+        filter.model
+    }
+}
+
+impl ScalarFilter<Float> for SqrtKalman {
+    fn model(&self) -> &ScalarKalman {
+        &self.model
+    }
+
+    fn predict_controlled(&mut self, u: Float) {
+        SqrtKalman::predict_controlled(self, u)
+    }
+
+    fn update(&mut self, z: Float) -> KalmanResult<Innovation> {
+        SqrtKalman::update(self, z)
+    }
+}
+
+#[cfg(feature = "python")]
+impl PyFilter for SqrtKalman {
+    type Inner = Self;
+
+    fn inner(&self) -> &Self {
+        self
+    }
+
+    fn inner_mut(&mut self) -> &mut Self {
+        self
+    }
+}
+
+/// Pickled form of a `SqrtKalman`: `A`, `H`, `Q`, `R`, `x`, `sqrt_P`, `B`, `tol`.
+#[cfg(feature = "python")]
+type SqrtState = (Float, Float, Float, Float, Float, Float, Float, Float);
+
+#[cfg(feature = "python")]
+scalar_pymethods!(SqrtKalman, Float, model, {
+    #[new]
+    #[allow(clippy::too_many_arguments)]
+    fn py_new(
+        A: Float,
+        H: Float,
+        Q: Float,
+        R: Float,
+        x0: Option<Float>,
+        P0: Option<Float>,
+        B: Option<Float>,
+        tol: Option<Float>,
+    ) -> PyResult<Self> {
+        let filter = Self::new(A, H, Q, R, x0, P0, B)?;
+        Ok(match tol {
+            Some(tol) => filter.with_tolerance(tol)?,
+            None => filter,
+        })
+    }
+    fn __repr__(&self) -> String {
+        let m = &self.model;
+        format!(
+            "SqrtKalman(A={:?}, H={:?}, Q={:?}, R={:?}, x0={:?}, P0={:?}, B={:?}, tol={:?})",
+            m.A, m.H, m.Q, m.R, m.x, m.P, m.B, m.tol
+        )
+    }
+    fn __getstate__(&self) -> SqrtState {
+        let m = &self.model;
+        (m.A, m.H, m.Q, m.R, m.x, self.sqrt_P, m.B, m.tol)
+    }
+    fn __setstate__(&mut self, state: SqrtState) -> PyResult<()> {
+        // Restoring `sqrt_P` itself keeps small variances exact across a round trip.
+        let (A, H, Q, R, x, sqrt_P, B, tol) = state;
+        let sqrt_P = check_variance("sqrt_P", sqrt_P)?;
+        let mut filter = Self::new(A, H, Q, R, Some(x), None, Some(B))?.with_tolerance(tol)?;
+        filter.set_sqrt_P(sqrt_P);
+        *self = filter;
+        Ok(())
+    }
+    fn __reduce__(&self, py: Python) -> (PyObject, (Float, Float, Float, Float), SqrtState) {
+        let m = &self.model;
+        (
+            py.get_type::<Self>().into(),
+            (m.A, m.H, m.Q, m.R),
+            self.__getstate__(),
+        )
+    }
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::UpdateForm;
+
+    /// Advance both filters over `zs`, asserting `x` and `P` agree to relative `tol` at
+    /// every step.
+    fn assert_agree(mut scalar: ScalarKalman, mut sqrt: SqrtKalman, zs: &[Float], tol: Float) {
+        let close = |a: Float, b: Float| (a - b).abs() <= tol * a.abs().max(b.abs());
+        for (k, &z) in zs.iter().enumerate() {
+            scalar.advance(z).unwrap();
+            sqrt.advance(z).unwrap();
+            assert!(
+                close(scalar.x(), sqrt.x()),
+                "x at step {k}: {} vs {}",
+                scalar.x(),
+                sqrt.x()
+            );
+            assert!(
+                close(scalar.P(), sqrt.P()),
+                "P at step {k}: {} vs {}",
+                scalar.P(),
+                sqrt.P()
+            );
+        }
+    }
+
+    fn series() -> Vec<Float> {
+        (0..50).map(|k| (0.3 * k as Float).sin()).collect()
+    }
+
+    #[test]
+    fn matches_scalar_filter() {
+        let scalar = ScalarKalman::new(0.95, 1.0, 0.1, 0.5, Some(0.2), Some(1.0), None).unwrap();
+        assert_agree(scalar.clone(), scalar.into(), &series(), 1e-12);
+    }
+
+    #[test]
+    fn matches_joseph_form_with_large_prior_and_small_noise() {
+        // The standard variance update loses most of its digits to cancellation here.
+        let scalar = ScalarKalman::new(1.0, 1.0, 1e-4, 1e-6, None, Some(1e8), None)
+            .unwrap()
+            .with_update_form(UpdateForm::Joseph);
+        assert_agree(scalar.clone(), scalar.into(), &series(), 1e-9);
+    }
+}
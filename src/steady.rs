@@ -0,0 +1,192 @@
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+#[cfg(feature = "python")]
+use crate::with_scalar_filter;
+use crate::{
+    check_finite, check_mask, FilterFloat, Float, KalmanError, KalmanResult, MissingPolicy,
+    ScalarFilter, ScalarKalman, SqrtKalman,
+};
+
+/// Limit reached by the variance and gain of a time-invariant filter.
+#[derive(Debug, Clone, Copy)]
+pub struct SteadyState<T = Float> {
+    /// Predicted variance, the solution of the discrete algebraic Riccati equation.
+    pub P_prior: T,
+    /// Filtered variance.
+    pub P: T,
+    /// Kalman gain.
+    pub K: T,
+}
+
+impl<T: FilterFloat> ScalarKalman<T> {
+    /// Solve the discrete algebraic Riccati equation for this filter's `A`, `H`, `Q`
+    /// and `R`.
+    ///
+    /// Fails if the state is unobservable (`H` is zero) and not stable (`|A| >= 1`),
+    /// since the variance then grows without bound.
+    pub fn steady_state(&self) -> KalmanResult<SteadyState<T>> {
+        let (A, H, Q, R) = (self.A, self.H, self.Q, self.R);
+        let two = T::of(2.0);
+        let H2 = H * H;
+        let P_prior = if H == T::zero() {
+            if A.abs() >= T::one() {
+                return Err(KalmanError::InvalidParameter {
+                    name: "A",
+                    value: A.into(),
+                });
+            }
+            Q / (T::one() - A * A)
+        } else {
+            // Non-negative root of H^2 P^2 + b P - Q R = 0, in whichever form avoids
+            // cancellation.
+            let b = R * (T::one() - A * A) - Q * H2;
+            let root = (b * b + T::of(4.0) * H2 * Q * R).sqrt();
+            if b < T::zero() {
+                (root - b) / (two * H2)
+            } else if b + root > T::zero() {
+                two * Q * R / (b + root)
+            } else {
+                T::zero()
+            }
+        };
+        let S = H2 * P_prior + R;
+        if S.abs() < self.tol {
+            return Err(KalmanError::FailedScalarInverse {
+                scalar_name: "Innovation (measurement pre-fit residual `S`)",
+            });
+        }
+        let K = P_prior * H / S;
+        Ok(SteadyState {
+            P_prior,
+            P: P_prior * R / S,
+            K,
+        })
+    }
+
+    /// Filter `zs` with the constant steady-state gain, skipping variance propagation.
+    ///
+    /// Missing samples are handled as in `filter_series`, and errors likewise report
+    /// their step. On return the filter holds the final state and the steady-state
+    /// filtered variance.
+    pub fn filter_fixed_gain(
+        &mut self,
+        zs: &[T],
+        mask: Option<&[bool]>,
+        policy: MissingPolicy,
+    ) -> KalmanResult<Vec<T>> {
+        check_mask(mask, zs.len())?;
+        let SteadyState { P, K, .. } = self.steady_state()?;
+        let out = zs
+            .iter()
+            .enumerate()
+            .map(|(i, &z)| {
+                let masked = mask.is_some_and(|mask| mask[i]);
+                if !masked && !z.is_nan() {
+                    if z.is_infinite() {
+                        let error = KalmanError::NonFiniteInput {
+                            name: "z",
+                            value: z.into(),
+                        };
+                        return Err(error.at_step(i));
+                    }
+                    let x = self.A * self.x;
+                    self.x =
+                        check_finite("x", x + K * (z - self.H * x)).map_err(|e| e.at_step(i))?;
+                } else if policy.predicts(i)? {
+                    self.x = self.A * self.x;
+                }
+                Ok(self.x)
+            })
+            .collect();
+        self.P = P;
+        out
+    }
+}
+
+impl SqrtKalman {
+    /// Solve the discrete algebraic Riccati equation, as [`ScalarKalman::steady_state`].
+    pub fn steady_state(&self) -> KalmanResult<SteadyState> {
+        self.model().steady_state()
+    }
+}
+
+/// Steady-state variance and gain of `filter`, returned as `(P, K)`.
+///
+/// `P` is the predicted variance solving the discrete algebraic Riccati equation; the
+/// filtered variance is `(1 - K H) P`.
+#[cfg(feature = "python")]
+#[pyfunction]
+pub(crate) fn ksteady(py: Python, filter: &PyAny) -> PyResult<PyObject> {
+    with_scalar_filter!(filter, |filter| {
+        let SteadyState { P_prior, K, .. } = filter.steady_state()?;
+        Ok((P_prior, K).into_py(py))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Relative residual of the Riccati equation `P = A^2 P R / (H^2 P + R) + Q`.
+    fn riccati_residual(filter: &ScalarKalman) -> Float {
+        let (A, H, Q, R) = (filter.A, filter.H, filter.Q, filter.R);
+        let P = filter.steady_state().unwrap().P_prior;
+        (A * A * P * R / (H * H * P + R) + Q - P).abs() / P
+    }
+
+    #[test]
+    fn solves_riccati_equation() {
+        // b < 0, b >= 0 and an unobservable but stable state.
+        for (A, H, Q, R) in [
+            (1.0, 1.0, 0.1, 0.5),
+            (0.5, 1.0, 0.01, 1.0),
+            (0.5, 0.0, 1.0, 1.0),
+        ] {
+            let filter = ScalarKalman::new(A, H, Q, R, None, None, None).unwrap();
+            let residual = riccati_residual(&filter);
+            assert!(residual < 1e-14, "residual {residual} for A={A}, H={H}");
+        }
+    }
+
+    #[test]
+    fn rejects_unobservable_unstable_state() {
+        let filter = ScalarKalman::new(1.0, 0.0, 0.1, 0.5, None, None, None).unwrap();
+        assert!(matches!(
+            filter.steady_state(),
+            Err(KalmanError::InvalidParameter { name: "A", .. })
+        ));
+    }
+
+    #[test]
+    fn fixed_gain_matches_converged_filter() {
+        let zs: Vec<Float> = (0..300).map(|k| (0.3 * k as Float).sin()).collect();
+        let mut filter = ScalarKalman::new(0.9, 1.0, 0.1, 0.5, None, Some(10.0), None).unwrap();
+        filter
+            .filter_series(&zs[..200], None, MissingPolicy::Predict)
+            .unwrap();
+        let mut fixed = filter.clone();
+        let xs = filter
+            .filter_series(&zs[200..], None, MissingPolicy::Predict)
+            .unwrap();
+        let fixed_xs = fixed
+            .filter_fixed_gain(&zs[200..], None, MissingPolicy::Predict)
+            .unwrap();
+        for (k, (x, fixed_x)) in xs.iter().zip(&fixed_xs).enumerate() {
+            assert!((x - fixed_x).abs() < 1e-12, "step {k}: {x} vs {fixed_x}");
+        }
+        assert!((filter.P() - fixed.P()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn fixed_gain_rejects_infinite_measurement() {
+        let mut filter = ScalarKalman::new(0.9, 1.0, 0.1, 0.5, None, None, None).unwrap();
+        let error = filter
+            .filter_fixed_gain(&[1.0, Float::INFINITY], None, MissingPolicy::Predict)
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            KalmanError::AtStep { index: 1, source } if matches!(*source, KalmanError::NonFiniteInput { .. })
+        ));
+    }
+}
@@ -0,0 +1,129 @@
+#[cfg(feature = "python")]
+use numpy::{PyArray1, PyReadonlyArray1};
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::types::PyDict;
+
+use crate::{
+    check_mask, step_or_missing, FilterFloat, Float, KalmanResult, MissingPolicy, ScalarFilter,
+    ScalarKalman, SqrtKalman,
+};
+#[cfg(feature = "python")]
+use crate::{read_mask, read_series, with_scalar_filter};
+
+/// Per-step filter quantities, one entry per measurement.
+///
+/// Steps without a measurement update report NaN for `K`, `y` and `S`, and zero for
+/// `loglik`.
+#[derive(Debug, Clone, Default)]
+pub struct Trajectory<T = Float> {
+    pub x: Vec<T>,
+    pub P: Vec<T>,
+    pub K: Vec<T>,
+    pub y: Vec<T>,
+    pub S: Vec<T>,
+    pub loglik: Vec<T>,
+}
+
+impl<T> Trajectory<T> {
+    fn with_capacity(len: usize) -> Self {
+        Self {
+            x: Vec::with_capacity(len),
+            P: Vec::with_capacity(len),
+            K: Vec::with_capacity(len),
+            y: Vec::with_capacity(len),
+            S: Vec::with_capacity(len),
+            loglik: Vec::with_capacity(len),
+        }
+    }
+}
+
+impl<T: FilterFloat> ScalarKalman<T> {
+    /// Filter `zs`, recording the state, variance, gain and innovation of every step.
+    pub fn trajectory(
+        &mut self,
+        zs: &[T],
+        mask: Option<&[bool]>,
+        policy: MissingPolicy,
+    ) -> KalmanResult<Trajectory<T>> {
+        trajectory(self, zs, mask, policy)
+    }
+}
+
+impl SqrtKalman {
+    /// Filter `zs`, recording every step as [`ScalarKalman::trajectory`].
+    pub fn trajectory(
+        &mut self,
+        zs: &[Float],
+        mask: Option<&[bool]>,
+        policy: MissingPolicy,
+    ) -> KalmanResult<Trajectory> {
+        trajectory(self, zs, mask, policy)
+    }
+}
+
+fn trajectory<T: FilterFloat>(
+    filter: &mut impl ScalarFilter<T>,
+    zs: &[T],
+    mask: Option<&[bool]>,
+    policy: MissingPolicy,
+) -> KalmanResult<Trajectory<T>> {
+    check_mask(mask, zs.len())?;
+    let mut out = Trajectory::with_capacity(zs.len());
+    for (i, &z) in zs.iter().enumerate() {
+        let masked = mask.is_some_and(|mask| mask[i]);
+        let innovation = step_or_missing(filter, z, masked, policy, i)?;
+        out.x.push(filter.model().x);
+        out.P.push(filter.model().P);
+        match innovation {
+            Some(inn) => {
+                out.K.push(inn.K);
+                out.y.push(inn.y);
+                out.S.push(inn.S);
+                out.loglik.push(inn.log_likelihood());
+            }
+            None => {
+                out.K.push(T::nan());
+                out.y.push(T::nan());
+                out.S.push(T::nan());
+                out.loglik.push(T::zero());
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// Filter a measurement series like `kfilter`, returning a dict of per-step arrays
+/// `x`, `P`, `K`, `y`, `S` and `loglik`.
+#[cfg(feature = "python")]
+#[pyfunction]
+pub(crate) fn kfilter_full<'py>(
+    py: Python<'py>,
+    filter: &PyAny,
+    v: &PyAny,
+    mask: Option<PyReadonlyArray1<bool>>,
+    missing: Option<&str>,
+) -> PyResult<&'py PyDict> {
+    let policy: MissingPolicy = missing.unwrap_or("predict").parse()?;
+    with_scalar_filter!(filter, |filter| {
+        let zs = read_series(v)?;
+        let mask = read_mask(mask, zs.len())?;
+        let Trajectory {
+            x,
+            P,
+            K,
+            y,
+            S,
+            loglik,
+        } = filter.trajectory(&zs, mask.as_deref(), policy)?;
+        let out = PyDict::new(py);
+        out.set_item("x", PyArray1::from_vec(py, x))?;
+        out.set_item("P", PyArray1::from_vec(py, P))?;
+        out.set_item("K", PyArray1::from_vec(py, K))?;
+        out.set_item("y", PyArray1::from_vec(py, y))?;
+        out.set_item("S", PyArray1::from_vec(py, S))?;
+        out.set_item("loglik", PyArray1::from_vec(py, loglik))?;
+        Ok(out)
+    })
+}
@@ -0,0 +1,422 @@
+use ndarray::{Array1, Array2, ArrayView1, Axis};
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray1, PyArray2, PyReadonlyArray1, PyReadonlyArray2};
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+#[cfg(feature = "python")]
+use crate::ekf::py_vector_fn;
+use crate::ekf::VectorFn;
+use crate::linalg::{cholesky, invert};
+use crate::multivariate::{check_shape, check_vec_len};
+use crate::{Float, KalmanError, KalmanResult};
+
+/// Scheme used to place the sigma points around the state estimate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SigmaPoints {
+    /// Van der Merwe's scaled sigma points: `2n + 1` points.
+    Merwe {
+        alpha: Float,
+        beta: Float,
+        kappa: Float,
+    },
+    /// Julier's original sigma points: `2n + 1` points.
+    Julier { kappa: Float },
+    /// Julier's spherical simplex sigma points: `n + 1` points.
+    Simplex,
+}
+
+impl SigmaPoints {
+    /// Build a scheme by name for an `n`-dimensional state. Unset parameters take the
+    /// usual defaults: `alpha = 1e-3`, `beta = 2`, and `kappa = 0` (Merwe) or
+    /// `kappa = 3 - n` (Julier). Fails if `alpha` is zero, `kappa` is `-n`, or a
+    /// parameter is given that the scheme does not use: Julier takes only `kappa` and
+    /// simplex takes none.
+    pub fn parse(
+        name: &str,
+        n: usize,
+        alpha: Option<Float>,
+        beta: Option<Float>,
+        kappa: Option<Float>,
+    ) -> KalmanResult<Self> {
+        // The weights divide by `alpha^2 (n + kappa)` or `n + kappa`.
+        let check_kappa = |kappa: Float| {
+            if kappa.is_nan() || n as Float + kappa == 0.0 {
+                return Err(KalmanError::InvalidParameter {
+                    name: "kappa",
+                    value: kappa,
+                });
+            }
+            Ok(kappa)
+        };
+        let unused = |name: &'static str, value: Option<Float>| match value {
+            Some(value) => Err(KalmanError::InvalidParameter { name, value }),
+            None => Ok(()),
+        };
+        match name {
+            "merwe" => {
+                let alpha = alpha.unwrap_or(1e-3);
+                if alpha.is_nan() || alpha == 0.0 {
+                    return Err(KalmanError::InvalidParameter {
+                        name: "alpha",
+                        value: alpha,
+                    });
+                }
+                Ok(Self::Merwe {
+                    alpha,
+                    beta: beta.unwrap_or(2.0),
+                    kappa: check_kappa(kappa.unwrap_or(0.0))?,
+                })
+            }
+            "julier" => {
+                unused("alpha", alpha)?;
+                unused("beta", beta)?;
+                Ok(Self::Julier {
+                    kappa: check_kappa(kappa.unwrap_or(3.0 - n as Float))?,
+                })
+            }
+            "simplex" => {
+                unused("alpha", alpha)?;
+                unused("beta", beta)?;
+                unused("kappa", kappa)?;
+                Ok(Self::Simplex)
+            }
+            _ => Err(KalmanError::InvalidOption {
+                name: "points",
+                value: name.to_owned(),
+            }),
+        }
+    }
+
+    /// Mean and covariance weights for an `n`-dimensional state.
+    fn weights(&self, n: usize) -> (Array1<Float>, Array1<Float>) {
+        let nf = n as Float;
+        match *self {
+            Self::Merwe { alpha, beta, kappa } => {
+                let lambda = alpha * alpha * (nf + kappa) - nf;
+                let mut Wm = Array1::from_elem(2 * n + 1, 0.5 / (nf + lambda));
+                let mut Wc = Wm.clone();
+                Wm[0] = lambda / (nf + lambda);
+                Wc[0] = Wm[0] + 1.0 - alpha * alpha + beta;
+                (Wm, Wc)
+            }
+            Self::Julier { kappa } => {
+                let mut W = Array1::from_elem(2 * n + 1, 0.5 / (nf + kappa));
+                W[0] = kappa / (nf + kappa);
+                (W.clone(), W)
+            }
+            Self::Simplex => {
+                let W = Array1::from_elem(n + 1, 1.0 / (nf + 1.0));
+                (W.clone(), W)
+            }
+        }
+    }
+
+    /// Sigma points for mean `x` and covariance `P`, one per row.
+    fn generate(&self, x: &Array1<Float>, P: &Array2<Float>) -> KalmanResult<Array2<Float>> {
+        let n = x.len();
+        let nf = n as Float;
+        let scale = match *self {
+            Self::Merwe { alpha, kappa, .. } => alpha * alpha * (nf + kappa),
+            Self::Julier { kappa } => nf + kappa,
+            Self::Simplex => 1.0,
+        };
+        let L = cholesky(&(P * scale)).ok_or(KalmanError::NotPositiveSemidefinite {
+            matrix_name: "State covariance `P`",
+        })?;
+        let offsets = match self {
+            Self::Simplex => {
+                // Rows of `L * I*`, where the columns of `I*` form a unit simplex.
+                let lambda = nf / (nf + 1.0);
+                let mut I = Array2::<Float>::zeros((n, n + 1));
+                for d in 1..=n {
+                    let df = d as Float;
+                    let c = 1.0 / (lambda * df * (df + 1.0)).sqrt();
+                    for j in 0..d {
+                        I[[d - 1, j]] = c;
+                    }
+                    I[[d - 1, d]] = -df * c;
+                }
+                (L.dot(&I) * nf.sqrt()).reversed_axes()
+            }
+            _ => {
+                let Lt = L.t();
+                let mut offsets = Array2::zeros((2 * n + 1, n));
+                for i in 0..n {
+                    offsets.row_mut(i + 1).assign(&Lt.row(i));
+                    offsets.row_mut(n + i + 1).assign(&-&Lt.row(i));
+                }
+                offsets
+            }
+        };
+        Ok(offsets + x)
+    }
+}
+
+/// Unscented Kalman filter with nonlinear state transition `f` and measurement `h`.
+#[cfg_attr(feature = "python", pyclass)]
+pub struct UnscentedKalman {
+    x: Array1<Float>,
+    P: Array2<Float>,
+    Q: Array2<Float>,
+    R: Array2<Float>,
+    f: VectorFn,
+    h: VectorFn,
+    points: SigmaPoints,
+    Wm: Array1<Float>,
+    Wc: Array1<Float>,
+}
+
+/// Weighted mean and centred deviations of a set of points, one per row.
+fn weighted_mean(Y: &Array2<Float>, Wm: &Array1<Float>) -> (Array1<Float>, Array2<Float>) {
+    let mean = Wm.dot(Y);
+    let dev = Y - &mean;
+    (mean, dev)
+}
+
+/// Weighted outer-product sum of two sets of deviations.
+fn weighted_cov(a: &Array2<Float>, b: &Array2<Float>, Wc: &Array1<Float>) -> Array2<Float> {
+    (a * &Wc.view().insert_axis(Axis(1))).t().dot(b)
+}
+
+impl UnscentedKalman {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        f: VectorFn,
+        h: VectorFn,
+        Q: Array2<Float>,
+        R: Array2<Float>,
+        x0: Option<Array1<Float>>,
+        P0: Option<Array2<Float>>,
+        points: SigmaPoints,
+    ) -> KalmanResult<Self> {
+        let n = Q.nrows();
+        let m = R.nrows();
+        check_shape("Q", &Q, n, n)?;
+        check_shape("R", &R, m, m)?;
+        let x = x0.unwrap_or_else(|| Array1::zeros(n));
+        check_vec_len("x0", &x.view(), n)?;
+        let P = P0.unwrap_or_else(|| Array2::zeros((n, n)));
+        check_shape("P0", &P, n, n)?;
+        let (Wm, Wc) = points.weights(n);
+        Ok(Self {
+            x,
+            P,
+            Q,
+            R,
+            f,
+            h,
+            points,
+            Wm,
+            Wc,
+        })
+    }
+
+    /// Apply `func` to every sigma point, checking each output has length `len`.
+    fn transform(
+        name: &'static str,
+        func: &VectorFn,
+        sigmas: &Array2<Float>,
+        len: usize,
+    ) -> KalmanResult<Array2<Float>> {
+        let mut out = Array2::zeros((sigmas.nrows(), len));
+        for (s, mut row) in sigmas.outer_iter().zip(out.outer_iter_mut()) {
+            let y = func(s)?;
+            check_vec_len(name, &y.view(), len)?;
+            row.assign(&y);
+        }
+        Ok(out)
+    }
+
+    /// Current state estimate.
+    pub fn x(&self) -> &Array1<Float> {
+        &self.x
+    }
+
+    /// Current state covariance.
+    pub fn P(&self) -> &Array2<Float> {
+        &self.P
+    }
+
+    pub fn predict(&mut self) -> KalmanResult<()> {
+        let n = self.x.len();
+        let sigmas = self.points.generate(&self.x, &self.P)?;
+        let Y = Self::transform("f(x)", &self.f, &sigmas, n)?;
+        let (x, dev) = weighted_mean(&Y, &self.Wm);
+        self.P = weighted_cov(&dev, &dev, &self.Wc) + &self.Q;
+        self.x = x;
+        Ok(())
+    }
+
+    pub fn update(&mut self, z: ArrayView1<Float>) -> KalmanResult<()> {
+        let m = self.R.nrows();
+        check_vec_len("z", &z, m)?;
+        let sigmas = self.points.generate(&self.x, &self.P)?;
+        let Z = Self::transform("h(x)", &self.h, &sigmas, m)?;
+        let (zp, Z_dev) = weighted_mean(&Z, &self.Wm);
+        let X_dev = &sigmas - &self.x;
+        let S = weighted_cov(&Z_dev, &Z_dev, &self.Wc) + &self.R;
+        let Pxz = weighted_cov(&X_dev, &Z_dev, &self.Wc);
+        let S_inv = invert(&S).ok_or(KalmanError::FailedMatrixInverse {
+            matrix_name: "Innovation (measurement pre-fit residual `S`)",
+        })?;
+        let K = Pxz.dot(&S_inv);
+        self.x += &K.dot(&(&z - &zp));
+        self.P = &self.P - &K.dot(&S).dot(&K.t());
+        Ok(())
+    }
+
+    pub fn advance(&mut self, z: ArrayView1<Float>) -> KalmanResult<&Array1<Float>> {
+        self.predict()?;
+        self.update(z)?;
+        Ok(&self.x)
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl UnscentedKalman {
+    #[new]
+    #[allow(clippy::too_many_arguments)]
+    fn py_new(
+        f: PyObject,
+        h: PyObject,
+        Q: PyReadonlyArray2<Float>,
+        R: PyReadonlyArray2<Float>,
+        x0: Option<PyReadonlyArray1<Float>>,
+        P0: Option<PyReadonlyArray2<Float>>,
+        points: Option<&str>,
+        alpha: Option<Float>,
+        beta: Option<Float>,
+        kappa: Option<Float>,
+    ) -> PyResult<Self> {
+        let points =
+            SigmaPoints::parse(points.unwrap_or("merwe"), Q.shape()[0], alpha, beta, kappa)?;
+        Ok(Self::new(
+            py_vector_fn("f", f),
+            py_vector_fn("h", h),
+            Q.to_owned_array(),
+            R.to_owned_array(),
+            x0.map(|x0| x0.to_owned_array()),
+            P0.map(|P0| P0.to_owned_array()),
+            points,
+        )?)
+    }
+    /// Current state estimate.
+    #[getter(x)]
+    fn get_x<'py>(&self, py: Python<'py>) -> &'py PyArray1<Float> {
+        self.x.clone().into_pyarray(py)
+    }
+    /// Current state covariance.
+    #[getter(P)]
+    fn get_P<'py>(&self, py: Python<'py>) -> &'py PyArray2<Float> {
+        self.P.clone().into_pyarray(py)
+    }
+    #[pyo3(name = "advance")]
+    fn py_advance<'py>(
+        &mut self,
+        py: Python<'py>,
+        z: PyReadonlyArray1<Float>,
+    ) -> PyResult<&'py PyArray1<Float>> {
+        Ok(self.advance(z.as_array())?.clone().into_pyarray(py))
+    }
+}
+
+/// Filter a series of measurement vectors with an unscented Kalman filter, one per row
+/// of `v`, returning one state estimate per row.
+#[cfg(feature = "python")]
+#[pyfunction]
+pub(crate) fn kfilter_ukf<'py>(
+    py: Python<'py>,
+    filter: &mut UnscentedKalman,
+    v: PyReadonlyArray2<Float>,
+) -> PyResult<&'py PyArray2<Float>> {
+    let v = v.as_array();
+    let mut out = Array2::zeros((v.nrows(), filter.x.len()));
+    for (z, mut row) in v.outer_iter().zip(out.outer_iter_mut()) {
+        row.assign(filter.advance(z)?);
+    }
+    Ok(out.into_pyarray(py))
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::array;
+
+    use super::*;
+    use crate::Kalman;
+
+    /// Run the unscented filter with `points` and a linear Kalman filter side by side on a
+    /// linear model, where the two must agree exactly.
+    fn assert_matches_linear_filter(points: SigmaPoints) {
+        let A = array![[1.0, 0.1], [0.0, 0.9]];
+        let H = array![[1.0, 0.5]];
+        let Q = array![[0.01, 0.0], [0.0, 0.04]];
+        let R = array![[0.25]];
+        let (x0, P0) = (array![0.5, -0.2], array![[1.0, 0.1], [0.1, 2.0]]);
+        let mut linear = Kalman::new(
+            A.clone(),
+            H.clone(),
+            Q.clone(),
+            R.clone(),
+            Some(x0.clone()),
+            Some(P0.clone()),
+        )
+        .unwrap();
+        let f: VectorFn = Box::new(move |x| Ok(A.dot(&x)));
+        let h: VectorFn = Box::new(move |x| Ok(H.dot(&x)));
+        let mut ukf = UnscentedKalman::new(f, h, Q, R, Some(x0), Some(P0), points).unwrap();
+        for k in 0..30 {
+            let z = array![(0.3 * k as Float).sin()];
+            linear.advance(z.view()).unwrap();
+            ukf.advance(z.view()).unwrap();
+            let dx = (ukf.x() - linear.x()).mapv(Float::abs).sum();
+            let dP = (ukf.P() - linear.P()).mapv(Float::abs).sum();
+            assert!(
+                dx < 1e-12 && dP < 1e-12,
+                "{points:?} at step {k}: {dx}, {dP}"
+            );
+        }
+    }
+
+    #[test]
+    fn merwe_points_match_linear_filter() {
+        // The default `alpha = 1e-3` makes weights of order 1e6 that amplify rounding.
+        let points = SigmaPoints::parse("merwe", 2, Some(0.5), None, Some(1.0)).unwrap();
+        assert_matches_linear_filter(points);
+    }
+
+    #[test]
+    fn julier_points_match_linear_filter() {
+        assert_matches_linear_filter(SigmaPoints::parse("julier", 2, None, None, None).unwrap());
+    }
+
+    #[test]
+    fn simplex_points_match_linear_filter() {
+        assert_matches_linear_filter(SigmaPoints::parse("simplex", 2, None, None, None).unwrap());
+    }
+
+    #[test]
+    fn rejects_singular_weights() {
+        assert!(matches!(
+            SigmaPoints::parse("merwe", 2, Some(0.0), None, None),
+            Err(KalmanError::InvalidParameter { name: "alpha", .. })
+        ));
+        assert!(matches!(
+            SigmaPoints::parse("julier", 2, None, None, Some(-2.0)),
+            Err(KalmanError::InvalidParameter { name: "kappa", .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_parameters_the_scheme_does_not_use() {
+        assert!(matches!(
+            SigmaPoints::parse("julier", 2, Some(0.1), None, None),
+            Err(KalmanError::InvalidParameter { name: "alpha", .. })
+        ));
+        assert!(matches!(
+            SigmaPoints::parse("simplex", 2, None, None, Some(1.0)),
+            Err(KalmanError::InvalidParameter { name: "kappa", .. })
+        ));
+    }
+}
@@ -0,0 +1,97 @@
+#[cfg(feature = "python")]
+use numpy::PyArray1;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+use crate::{check_len, check_variance, FilterFloat, Float, KalmanResult, ScalarKalman};
+#[cfg(feature = "python")]
+use crate::{read_series, with_scalar_filter, PyScalarKalman, PyScalarKalman32};
+
+/// Per-step model parameters; `None` keeps the filter's own value for every step.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Schedule<'a, T = Float> {
+    pub A: Option<&'a [T]>,
+    pub H: Option<&'a [T]>,
+    pub Q: Option<&'a [T]>,
+    pub R: Option<&'a [T]>,
+}
+
+impl<T: FilterFloat> ScalarKalman<T> {
+    /// Filter `zs`, taking step `k`'s parameters from `schedule` where given.
+    ///
+    /// The filter's own parameters are restored afterwards; only the state and
+    /// variance carry over.
+    pub fn filter_varying(&mut self, zs: &[T], schedule: Schedule<T>) -> KalmanResult<Vec<T>> {
+        let len = zs.len();
+        for (name, series) in [
+            ("A", schedule.A),
+            ("H", schedule.H),
+            ("Q", schedule.Q),
+            ("R", schedule.R),
+        ] {
+            if let Some(series) = series {
+                check_len(name, series.len(), len)?;
+            }
+        }
+
+        let (A, H, Q, R) = (self.A, self.H, self.Q, self.R);
+        let out = self.filter_varying_unchecked(zs, schedule);
+        self.A = A;
+        self.H = H;
+        self.Q = Q;
+        self.R = R;
+        out
+    }
+
+    fn filter_varying_unchecked(
+        &mut self,
+        zs: &[T],
+        schedule: Schedule<T>,
+    ) -> KalmanResult<Vec<T>> {
+        let mut out = Vec::with_capacity(zs.len());
+        for (k, &z) in zs.iter().enumerate() {
+            if let Some(A) = schedule.A {
+                self.A = A[k];
+            }
+            if let Some(H) = schedule.H {
+                self.H = H[k];
+            }
+            if let Some(Q) = schedule.Q {
+                self.Q = check_variance("Q", Q[k]).map_err(|e| e.at_step(k))?;
+            }
+            if let Some(R) = schedule.R {
+                self.R = check_variance("R", R[k]).map_err(|e| e.at_step(k))?;
+            }
+            out.push(self.advance(z).map_err(|e| e.at_step(k))?);
+        }
+        Ok(out)
+    }
+}
+
+/// Filter a measurement series with any subset of `A`, `H`, `Q` and `R` given as
+/// per-step arrays the same length as `v`.
+#[cfg(feature = "python")]
+#[pyfunction]
+pub(crate) fn kfilter_varying<'py>(
+    py: Python<'py>,
+    filter: &PyAny,
+    v: &PyAny,
+    A: Option<&PyAny>,
+    H: Option<&PyAny>,
+    Q: Option<&PyAny>,
+    R: Option<&PyAny>,
+) -> PyResult<&'py PyAny> {
+    with_scalar_filter!(filter, [PyScalarKalman, PyScalarKalman32], |filter| {
+        let zs = read_series(v)?;
+        let read = |a: Option<&PyAny>| a.map(read_series).transpose();
+        let (A, H, Q, R) = (read(A)?, read(H)?, read(Q)?, read(R)?);
+        let schedule = Schedule {
+            A: A.as_deref(),
+            H: H.as_deref(),
+            Q: Q.as_deref(),
+            R: R.as_deref(),
+        };
+        let out = filter.filter_varying(&zs, schedule)?;
+        Ok(PyArray1::from_vec(py, out).as_ref())
+    })
+}